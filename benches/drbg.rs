@@ -0,0 +1,31 @@
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use random_numbers::drbg::build_generators;
+use random_numbers::{DEFAULT_LENGTHS, DEFAULT_SEED};
+use std::hint::black_box;
+use std::time::Duration;
+
+fn generate_bits(c: &mut Criterion) {
+    for mut drbg in build_generators(DEFAULT_SEED) {
+        let mut group = c.benchmark_group(drbg.name());
+        // The slowest generators need seconds per 10^7 bits; keep the sample
+        // count at Criterion's minimum and let the measurement time stretch.
+        group.sample_size(10);
+        group.warm_up_time(Duration::from_secs(1));
+        group.measurement_time(Duration::from_secs(3));
+        for bits in DEFAULT_LENGTHS {
+            group.throughput(Throughput::Bytes(bits.div_ceil(8) as u64));
+            group.bench_with_input(BenchmarkId::from_parameter(bits), &bits, |b, &bits| {
+                b.iter(|| drbg.generate_bits(black_box(bits)))
+            });
+            if drbg.supports_parallel() {
+                group.bench_with_input(BenchmarkId::new("parallel", bits), &bits, |b, &bits| {
+                    b.iter(|| drbg.generate_bits_parallel(black_box(bits)))
+                });
+            }
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, generate_bits);
+criterion_main!(benches);
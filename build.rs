@@ -0,0 +1,41 @@
+//! Embeds the compiler version and the git commit the binary is built from,
+//! for `environment.json`.
+
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn main() {
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    if let Some(version) = output(&rustc, &["--version"]) {
+        println!("cargo:rustc-env=BUILD_RUSTC_VERSION={version}");
+    }
+    if let Some(commit) = output("git", &["rev-parse", "HEAD"]) {
+        println!("cargo:rustc-env=BUILD_GIT_COMMIT={commit}");
+    }
+
+    // Rerun when HEAD moves: on checkout (HEAD itself) and on commit (the
+    // branch it points to). Paths that do not exist would rerun every build.
+    println!("cargo:rerun-if-changed=build.rs");
+    let mut watched = vec![".git/HEAD".to_string(), ".git/packed-refs".to_string()];
+    if let Some(branch) = fs::read_to_string(".git/HEAD")
+        .ok()
+        .and_then(|head| Some(head.strip_prefix("ref: ")?.trim().to_string()))
+    {
+        watched.push(format!(".git/{branch}"));
+    }
+    for path in watched {
+        if Path::new(&path).exists() {
+            println!("cargo:rerun-if-changed={path}");
+        }
+    }
+}
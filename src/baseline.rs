@@ -0,0 +1,114 @@
+//! Named baselines of `summary.csv` for regression checks: a run can save
+//! its summary under a name, and a later run compares its own summary
+//! against it, configuration by configuration.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+const BASELINE_DIR: &str = "results/baselines";
+// Compared column: the median is the timing statistic least moved by noise
+// on shared CI machines.
+const METRIC: &str = "median_time_ms";
+
+/// Change of one configuration against the baseline.
+pub struct Delta {
+    pub generator: String,
+    pub bits: usize,
+    pub baseline_ms: f64,
+    pub current_ms: f64,
+    /// Positive when the current run is slower.
+    pub delta_percent: f64,
+}
+
+fn path(name: &str) -> PathBuf {
+    Path::new(BASELINE_DIR).join(format!("{name}.csv"))
+}
+
+/// Stores `summary` as baseline `name`, replacing any earlier one.
+pub fn save(name: &str, summary: &Path) -> Result<PathBuf, Box<dyn Error>> {
+    fs::create_dir_all(BASELINE_DIR)?;
+    let target = path(name);
+    fs::copy(summary, &target)?;
+    Ok(target)
+}
+
+/// `METRIC` per (generator, bits), located by column name so baselines from
+/// older layouts of `summary.csv` still load.
+fn load(path: &Path) -> Result<BTreeMap<(String, usize), f64>, Box<dyn Error>> {
+    let contents = fs::read_to_string(path)
+        .map_err(|e| format!("cannot read baseline {}: {e}", path.display()))?;
+    let mut lines = contents.lines();
+    let header: Vec<&str> = lines.next().unwrap_or_default().split(',').collect();
+    let column = |name: &str| {
+        header
+            .iter()
+            .position(|&h| h == name)
+            .ok_or_else(|| format!("{} has no {name} column", path.display()))
+    };
+    let (generator, bits, metric) = (column("generator")?, column("bits")?, column(METRIC)?);
+
+    let mut values = BTreeMap::new();
+    for line in lines {
+        let fields: Vec<&str> = line.split(',').collect();
+        values.insert(
+            (fields[generator].to_string(), fields[bits].parse()?),
+            fields[metric].parse()?,
+        );
+    }
+    Ok(values)
+}
+
+/// Fails if baseline `name` is missing or unreadable, so a run can check it
+/// before spending time on the benchmark.
+pub fn check(name: &str) -> Result<(), Box<dyn Error>> {
+    load(&path(name)).map(|_| ())
+}
+
+/// Deltas for every configuration present in both baseline `name` and the
+/// `current` summary.
+pub fn compare(name: &str, current: &Path) -> Result<Vec<Delta>, Box<dyn Error>> {
+    let baseline = load(&path(name))?;
+    let current = load(current)?;
+    Ok(current
+        .into_iter()
+        .filter_map(|(key, current_ms)| {
+            let baseline_ms = *baseline.get(&key)?;
+            let (generator, bits) = key;
+            Some(Delta {
+                generator,
+                bits,
+                baseline_ms,
+                current_ms,
+                delta_percent: 100.0 * (current_ms - baseline_ms) / baseline_ms,
+            })
+        })
+        .collect())
+}
+
+pub fn write_csv(
+    dir: &Path,
+    deltas: &[Delta],
+    threshold_percent: f64,
+) -> Result<(), Box<dyn Error>> {
+    let mut file = File::create(dir.join("baseline_comparison.csv"))?;
+    writeln!(
+        file,
+        "generator,bits,baseline_{METRIC},current_{METRIC},delta_percent,regressed"
+    )?;
+    for d in deltas {
+        writeln!(
+            file,
+            "{},{},{:.6},{:.6},{:.2},{}",
+            d.generator,
+            d.bits,
+            d.baseline_ms,
+            d.current_ms,
+            d.delta_percent,
+            d.delta_percent > threshold_percent
+        )?;
+    }
+    Ok(())
+}
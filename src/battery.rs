@@ -0,0 +1,309 @@
+use crate::drbg::BitString;
+use crate::stats::{
+    self, AutocorrelationResult, BirthdaySpacingsResult, ChiSquareResult, CusumResult,
+    RandomExcursionsResult, RunsResult, SerialResult,
+};
+use serde::{Deserialize, Serialize};
+
+// SP 800-22 §4.2.2: p-values across sequences count as uniform above this.
+const UNIFORMITY_THRESHOLD: f64 = 0.0001;
+// False discovery rate for the Benjamini–Hochberg corrected verdicts.
+const FALSE_DISCOVERY_RATE: f64 = 0.01;
+
+/// Named p-values of one sequence; `None` where a test was not applicable.
+pub type PValues = Vec<(String, Option<f64>)>;
+
+/// Every SP 800-22 test result for one bit string.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct StsResults {
+    pub monobit_p: f64,
+    pub block_frequency_p: f64,
+    pub runs: RunsResult,
+    pub longest_run_p: f64,
+    pub matrix_rank_p: f64,
+    pub spectral_p: f64,
+    /// One p-value per template of `stats::aperiodic_templates`.
+    pub template_p: Vec<f64>,
+    pub overlapping_template_p: f64,
+    pub maurer_p: Option<f64>,
+    /// Mean of the per-block LFSR lengths; the lengths themselves are dropped.
+    pub mean_lfsr_length: f64,
+    pub linear_complexity_p: f64,
+    pub serial: SerialResult,
+    pub approximate_entropy_p: f64,
+    pub cusum: CusumResult,
+    pub excursions: RandomExcursionsResult,
+}
+
+/// Results of the extended battery: tests from outside SP 800-22.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ExtendedResults {
+    pub byte_chi_square: ChiSquareResult,
+    pub poker: ChiSquareResult,
+    pub autocorrelation: Vec<AutocorrelationResult>,
+    pub birthday_spacings: BirthdaySpacingsResult,
+    pub gap: Option<ChiSquareResult>,
+    pub overlapping_permutations: Option<ChiSquareResult>,
+}
+
+/// Pass/fail decision for one (sub-)test across all sequences of a generator.
+pub struct Verdict {
+    pub test: String,
+    /// Sequences for which the test was applicable.
+    pub sequences: usize,
+    pub passed: usize,
+    pub proportion: f64,
+    /// Lower end of the acceptable proportion interval (SP 800-22 §4.2.1).
+    pub min_proportion: f64,
+    /// Second-level chi-square (10 bins) p-value of the sequences' p-values.
+    pub uniformity_p: f64,
+    /// Second-level Kolmogorov–Smirnov p-value; reported, not used for `pass`.
+    pub ks_p: f64,
+    /// Binomial probability of at most `passed` passes out of `sequences`
+    /// when each passes with probability 1 - α.
+    pub proportion_p: f64,
+    /// Raw test-level p-value: both criteria combined with a Bonferroni factor 2.
+    pub p_value: f64,
+    /// `p_value` after Benjamini–Hochberg over all tests of the generator.
+    pub adjusted_p: f64,
+    /// The STS decision on the raw criteria.
+    pub pass: bool,
+    /// Decision on `adjusted_p` at the false discovery rate.
+    pub adjusted_pass: bool,
+}
+
+/// Runs the whole SP 800-22 battery on one bit string.
+pub fn run(bits: &BitString, block_frequency_m: usize, approximate_entropy_m: usize) -> StsResults {
+    let linear_complexity = traced("linear_complexity", || stats::linear_complexity(bits));
+    let lengths = &linear_complexity.lengths;
+    StsResults {
+        monobit_p: traced("monobit", || stats::monobit(bits)),
+        block_frequency_p: traced("block_frequency", || {
+            stats::block_frequency(bits, block_frequency_m)
+        }),
+        runs: traced("runs", || stats::runs(bits)),
+        longest_run_p: traced("longest_run", || stats::longest_run(bits)),
+        matrix_rank_p: traced("matrix_rank", || stats::matrix_rank(bits)),
+        spectral_p: traced("spectral", || stats::spectral(bits)),
+        template_p: traced("non_overlapping_templates", || {
+            stats::non_overlapping_templates(bits)
+        }),
+        overlapping_template_p: traced("overlapping_template", || {
+            stats::overlapping_template(bits)
+        }),
+        maurer_p: traced("maurer_universal", || stats::maurer_universal(bits)),
+        mean_lfsr_length: lengths.iter().sum::<usize>() as f64 / lengths.len().max(1) as f64,
+        linear_complexity_p: linear_complexity.p_value,
+        serial: traced("serial", || stats::serial(bits)),
+        approximate_entropy_p: traced("approximate_entropy", || {
+            stats::approximate_entropy(bits, approximate_entropy_m)
+        }),
+        cusum: traced("cusum", || stats::cusum(bits)),
+        excursions: traced("random_excursions", || stats::random_excursions(bits)),
+    }
+}
+
+/// Runs the extended battery, with the autocorrelation test at every shift.
+pub fn run_extended(bits: &BitString, autocorrelation_shifts: &[usize]) -> ExtendedResults {
+    ExtendedResults {
+        byte_chi_square: traced("byte_chi_square", || stats::byte_chi_square(bits)),
+        poker: traced("poker", || stats::poker(bits)),
+        autocorrelation: traced("autocorrelation", || {
+            autocorrelation_shifts
+                .iter()
+                .map(|&shift| stats::autocorrelation(bits, shift))
+                .collect()
+        }),
+        birthday_spacings: traced("birthday_spacings", || stats::birthday_spacings(bits)),
+        gap: traced("gap", || stats::gap(bits)),
+        overlapping_permutations: traced("overlapping_permutations", || {
+            stats::overlapping_permutations(bits)
+        }),
+    }
+}
+
+/// Runs one test inside a trace-level `test` span, so `-vvv` logs the time
+/// each test takes.
+fn traced<T>(name: &'static str, test: impl FnOnce() -> T) -> T {
+    tracing::trace_span!("test", name).in_scope(test)
+}
+
+impl StsResults {
+    /// Every p-value under its STS test name, with templates and excursion
+    /// states as separate sub-tests; `None` where the test was not applicable.
+    pub fn p_values(&self) -> PValues {
+        let mut p_values = vec![
+            ("Frequency".to_string(), Some(self.monobit_p)),
+            ("BlockFrequency".to_string(), Some(self.block_frequency_p)),
+            ("Runs".to_string(), Some(self.runs.p_value)),
+            ("LongestRun".to_string(), Some(self.longest_run_p)),
+            ("Rank".to_string(), Some(self.matrix_rank_p)),
+            ("FFT".to_string(), Some(self.spectral_p)),
+        ];
+        let templates = stats::aperiodic_templates(stats::TEMPLATE_LENGTH);
+        for (template, &p) in templates.iter().zip(&self.template_p) {
+            p_values.push((
+                format!(
+                    "NonOverlappingTemplate[{template:0width$b}]",
+                    width = stats::TEMPLATE_LENGTH
+                ),
+                Some(p),
+            ));
+        }
+        p_values.extend([
+            (
+                "OverlappingTemplate".to_string(),
+                Some(self.overlapping_template_p),
+            ),
+            ("Universal".to_string(), self.maurer_p),
+            (
+                "LinearComplexity".to_string(),
+                Some(self.linear_complexity_p),
+            ),
+            ("Serial[1]".to_string(), Some(self.serial.p_value_1)),
+            ("Serial[2]".to_string(), Some(self.serial.p_value_2)),
+            (
+                "ApproximateEntropy".to_string(),
+                Some(self.approximate_entropy_p),
+            ),
+            (
+                "CumulativeSums[forward]".to_string(),
+                Some(self.cusum.forward_p),
+            ),
+            (
+                "CumulativeSums[backward]".to_string(),
+                Some(self.cusum.backward_p),
+            ),
+        ]);
+        for (i, x) in stats::EXCURSION_STATES.iter().enumerate() {
+            let p = self.excursions.excursion_p.map(|p| p[i]);
+            p_values.push((format!("RandomExcursions[x={x}]"), p));
+        }
+        for (i, x) in stats::EXCURSION_VARIANT_STATES.iter().enumerate() {
+            let p = self.excursions.variant_p.map(|p| p[i]);
+            p_values.push((format!("RandomExcursionsVariant[x={x}]"), p));
+        }
+        p_values
+    }
+}
+
+impl ExtendedResults {
+    /// Every p-value under its test name, in the same form as
+    /// [`StsResults::p_values`]; birthday spacings, gap and overlapping
+    /// permutations need a minimum number of words.
+    pub fn p_values(&self) -> PValues {
+        let mut p_values = vec![
+            (
+                "ByteChiSquare".to_string(),
+                Some(self.byte_chi_square.p_value),
+            ),
+            ("Poker".to_string(), Some(self.poker.p_value)),
+        ];
+        for a in &self.autocorrelation {
+            p_values.push((format!("Autocorrelation[d={}]", a.shift), Some(a.p_value)));
+        }
+        let birthday = &self.birthday_spacings;
+        p_values.push((
+            "BirthdaySpacings".to_string(),
+            (birthday.samples > 0).then_some(birthday.p_value),
+        ));
+        p_values.push(("Gap".to_string(), self.gap.map(|g| g.p_value)));
+        p_values.push((
+            "OverlappingPermutations".to_string(),
+            self.overlapping_permutations.map(|o| o.p_value),
+        ));
+        p_values
+    }
+}
+
+/// Applies the two STS acceptance criteria to the same generator's results:
+/// the proportion of sequences passing at α must lie within
+/// (1 - α) ± 3·√(α(1 - α)/m), and the p-values must be uniform (§4.2.2).
+/// With a couple of hundred tests per generator some raw failures are
+/// expected by chance, so each verdict also carries a Benjamini–Hochberg
+/// corrected decision.
+/// SP 800-22 asks for at least 55 sequences for the uniformity check to be
+/// reliable. Tests that were applicable to no sequence get no verdict. Every
+/// sequence must list the same tests in the same order, as `p_values` does.
+pub fn evaluate(per_sequence: &[PValues]) -> Vec<Verdict> {
+    let Some(first) = per_sequence.first() else {
+        return Vec::new();
+    };
+
+    let alpha = stats::SIGNIFICANCE_LEVEL;
+    let mut verdicts = Vec::new();
+    for (i, (test, _)) in first.iter().enumerate() {
+        let p_values: Vec<f64> = per_sequence.iter().filter_map(|p| p[i].1).collect();
+        if p_values.is_empty() {
+            continue;
+        }
+        let sequences = p_values.len();
+        let passed = p_values.iter().filter(|&&p| p >= alpha).count();
+        let proportion = passed as f64 / sequences as f64;
+        let min_proportion =
+            (1.0 - alpha) - 3.0 * (alpha * (1.0 - alpha) / sequences as f64).sqrt();
+        let uniformity_p = stats::uniformity(&p_values);
+        let proportion_p = stats::binomial_cdf(passed, sequences, 1.0 - alpha);
+        verdicts.push(Verdict {
+            test: test.clone(),
+            sequences,
+            passed,
+            proportion,
+            min_proportion,
+            uniformity_p,
+            ks_p: stats::ks_uniformity(&p_values),
+            proportion_p,
+            p_value: (2.0 * proportion_p.min(uniformity_p)).min(1.0),
+            adjusted_p: 1.0,
+            pass: proportion >= min_proportion && uniformity_p >= UNIFORMITY_THRESHOLD,
+            adjusted_pass: true,
+        });
+    }
+
+    let raw: Vec<f64> = verdicts.iter().map(|v| v.p_value).collect();
+    for (v, adjusted) in verdicts.iter_mut().zip(benjamini_hochberg(&raw)) {
+        v.adjusted_p = adjusted;
+        v.adjusted_pass = adjusted >= FALSE_DISCOVERY_RATE;
+    }
+    verdicts
+}
+
+/// Benjamini–Hochberg adjusted p-values (step-up), in the input order.
+fn benjamini_hochberg(p_values: &[f64]) -> Vec<f64> {
+    let m = p_values.len();
+    let mut order: Vec<usize> = (0..m).collect();
+    order.sort_by(|&a, &b| p_values[a].total_cmp(&p_values[b]));
+
+    let mut adjusted = vec![1.0; m];
+    let mut running_min = 1.0f64;
+    for (rank, &i) in order.iter().enumerate().rev() {
+        running_min = running_min.min(p_values[i] * m as f64 / (rank + 1) as f64);
+        adjusted[i] = running_min;
+    }
+    adjusted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_all_close(actual: &[f64], expected: &[f64]) {
+        assert_eq!(actual.len(), expected.len());
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() < 1e-12, "{actual:?} != {expected:?}");
+        }
+    }
+
+    /// Ranks 1..4 are 0.01, 0.03, 0.04, 0.5: the step-up minimum carries
+    /// 0.04·4/3 down to rank 2, and the result is back in input order.
+    #[test]
+    fn benjamini_hochberg_by_hand() {
+        assert_all_close(
+            &benjamini_hochberg(&[0.01, 0.04, 0.03, 0.5]),
+            &[0.04, 0.04 * 4.0 / 3.0, 0.04 * 4.0 / 3.0, 0.5],
+        );
+        // p·m/rank above 1 stays capped by the running minimum.
+        assert_all_close(&benjamini_hochberg(&[0.9, 0.8]), &[0.9, 0.9]);
+        assert!(benjamini_hochberg(&[]).is_empty());
+    }
+}
@@ -0,0 +1,107 @@
+//! Incremental record log of a sweep, for `bench --resume`. Every finished
+//! measurement is appended to `checkpoint.msgpack` in the output directory
+//! straight away, so an interrupted sweep loses at most the measurement in
+//! progress. MessagePack rather than JSON lines, because JSON has no NaN and
+//! the records must read back exactly as they were measured.
+
+use crate::Record;
+use crate::config::{Config, Tests};
+use rmp_serde::decode;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fs::{self, File, OpenOptions};
+use std::io::{Cursor, ErrorKind, Write};
+use std::path::Path;
+
+const FILE_NAME: &str = "checkpoint.msgpack";
+
+/// First entry of the log: the settings a measurement depends on besides
+/// its (generator, length, run) cell. Lengths and runs may change between
+/// sessions; cells outside the new sweep are simply not reused.
+#[derive(Serialize, Deserialize)]
+struct Header {
+    seed: Vec<u8>,
+    tests: Tests,
+}
+
+pub struct Checkpoint {
+    file: File,
+}
+
+fn encode<T: Serialize>(value: &T) -> Result<Vec<u8>, Box<dyn Error>> {
+    Ok(rmp_serde::to_vec_named(value)?)
+}
+
+impl Checkpoint {
+    /// Starts an empty log for `config`, replacing any earlier one.
+    pub fn create(dir: &Path, config: &Config) -> Result<Checkpoint, Box<dyn Error>> {
+        let mut file = File::create(dir.join(FILE_NAME))?;
+        file.write_all(&encode(&Header {
+            seed: config.seed.clone(),
+            tests: config.tests,
+        })?)?;
+        Ok(Checkpoint { file })
+    }
+
+    /// Reopens the log of an earlier sweep with the same seed and tests and
+    /// returns its records. A record cut short by the interruption is
+    /// dropped and truncated away. Without a log, starts an empty one.
+    pub fn resume(
+        dir: &Path,
+        config: &Config,
+    ) -> Result<(Checkpoint, Vec<Record>), Box<dyn Error>> {
+        let path = dir.join(FILE_NAME);
+        let bytes = match fs::read(&path) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                tracing::warn!("no checkpoint in {}, starting afresh", dir.display());
+                return Ok((Checkpoint::create(dir, config)?, Vec::new()));
+            }
+            Err(e) => return Err(format!("cannot read {}: {e}", path.display()).into()),
+        };
+        let mut cursor = Cursor::new(bytes.as_slice());
+        let header: Header = rmp_serde::from_read(&mut cursor)
+            .map_err(|e| format!("{}: not a checkpoint: {e}", path.display()))?;
+        if header.seed != config.seed || header.tests != config.tests {
+            return Err(format!(
+                "cannot resume from {}: it was taken with a different seed or test selection",
+                path.display()
+            )
+            .into());
+        }
+
+        let mut records = Vec::new();
+        let mut valid = cursor.position();
+        while (valid as usize) < bytes.len() {
+            match rmp_serde::from_read::<_, Record>(&mut cursor) {
+                Ok(record) => {
+                    records.push(record);
+                    valid = cursor.position();
+                }
+                // The file ends inside the record: the write was cut short.
+                Err(decode::Error::InvalidMarkerRead(e) | decode::Error::InvalidDataRead(e))
+                    if e.kind() == ErrorKind::UnexpectedEof =>
+                {
+                    break;
+                }
+                Err(e) => return Err(format!("{}: {e}", path.display()).into()),
+            }
+        }
+        if (valid as usize) < bytes.len() {
+            tracing::warn!(
+                "dropping {} bytes of an incomplete record from {}",
+                bytes.len() - valid as usize,
+                path.display()
+            );
+        }
+        let file = OpenOptions::new().append(true).open(&path)?;
+        file.set_len(valid)?;
+        Ok((Checkpoint { file }, records))
+    }
+
+    /// Appends `record` in a single write.
+    pub fn append(&mut self, record: &Record) -> Result<(), Box<dyn Error>> {
+        self.file.write_all(&encode(record)?)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,266 @@
+//! Command-line interface. Without a subcommand the binary runs `bench`, so
+//! `cargo run --release -- --runs 3` keeps working.
+
+use crate::config::{
+    Config, os_entropy_seed, parse_count, parse_hex, parse_positive, parse_size, parse_tag, to_hex,
+};
+use crate::workload::{DEFAULT_DISTRIBUTION, RequestSizes};
+use crate::{REGRESSION_THRESHOLD_PERCENT, SEED_SWEEP_SEEDS};
+use clap::{ArgAction, Args, Parser, Subcommand, ValueEnum};
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+
+#[derive(Parser)]
+#[command(
+    about = "Benchmarks and statistical tests for deterministic random bit generators",
+    args_conflicts_with_subcommands = true
+)]
+pub struct Cli {
+    /// Log progress to stderr: -v for phases, -vv for every measurement,
+    /// -vvv for every statistical test
+    #[arg(short, long, action = ArgAction::Count, global = true)]
+    pub verbose: u8,
+    #[command(subcommand)]
+    pub command: Option<Command>,
+    #[command(flatten)]
+    pub bench: BenchArgs,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Time every generator over the target lengths and runs (the default)
+    Bench(BenchArgs),
+    /// Run the statistical battery on every generator's output, untimed
+    Test(ExperimentArgs),
+    /// Write raw output of one generator to a file or stdout
+    Generate(GenerateArgs),
+    /// Redraw the summary plots from an existing summary.csv
+    Plot(ResultsArgs),
+    /// Print a per-length comparison table from an existing summary.csv
+    Report(ResultsArgs),
+    /// Print every available generator with its key and block sizes and
+    /// security strength
+    ListGenerators,
+}
+
+/// Where an experiment is described and where its results go.
+#[derive(Args)]
+pub struct ResultsArgs {
+    /// TOML experiment file; the flags below override its keys
+    #[arg(long, value_name = "FILE")]
+    config: Option<PathBuf>,
+    /// Directory for results and plots [default: results]
+    #[arg(long, value_name = "DIR")]
+    output_dir: Option<PathBuf>,
+}
+
+impl ResultsArgs {
+    /// The experiment file's configuration, or the defaults without one.
+    pub fn config(&self) -> Result<Config, Box<dyn Error>> {
+        let mut config = match &self.config {
+            Some(path) => Config::load(path)?,
+            None => Config::default(),
+        };
+        if let Some(dir) = &self.output_dir {
+            config.output_dir = dir.clone();
+        }
+        Ok(config)
+    }
+}
+
+/// Parameters shared by everything that sweeps generators and lengths.
+#[derive(Args)]
+pub struct ExperimentArgs {
+    #[command(flatten)]
+    results: ResultsArgs,
+    /// Comma-separated target lengths in bits, e.g. 10_000,1_000_000
+    #[arg(long, value_delimiter = ',', value_parser = parse_count)]
+    lengths: Option<Vec<usize>>,
+    /// Measured repetitions per (generator, length)
+    #[arg(long, value_parser = parse_count)]
+    runs: Option<usize>,
+    /// Untimed generations per (generator, length) before the measured runs [default: 3]
+    #[arg(long, value_name = "RUNS")]
+    warmup_runs: Option<usize>,
+    /// Outlier cut-off for timings, in scaled MADs from their group's median [default: 3]
+    #[arg(long, value_name = "MADS", value_parser = parse_positive)]
+    outlier_mads: Option<f64>,
+    #[command(flatten)]
+    seed: SeedArgs,
+    /// Comma-separated generator names or slugs to run, leaving out the rest
+    #[arg(long, value_delimiter = ',', value_name = "GENERATORS")]
+    only: Option<Vec<String>>,
+    /// Comma-separated generator names or slugs to leave out
+    #[arg(long, value_delimiter = ',', value_name = "GENERATORS")]
+    exclude: Option<Vec<String>>,
+    /// Label stored in every record and summary row, e.g. before-optimization
+    #[arg(long, value_parser = parse_tag)]
+    tag: Option<String>,
+}
+
+impl ExperimentArgs {
+    /// The configuration of [`ResultsArgs::config`], overridden by the
+    /// given flags.
+    pub fn config(&self) -> Result<Config, Box<dyn Error>> {
+        let mut config = self.results.config()?;
+        if let Some(lengths) = &self.lengths {
+            config.set_lengths(lengths.clone());
+        }
+        if let Some(runs) = self.runs {
+            config.runs = runs;
+        }
+        if let Some(warmup_runs) = self.warmup_runs {
+            config.warmup_runs = warmup_runs;
+        }
+        if let Some(outlier_mads) = self.outlier_mads {
+            config.outlier_mads = outlier_mads;
+        }
+        if let Some(seed) = self.seed.seed()? {
+            config.seed = seed;
+        }
+        if let Some(only) = &self.only {
+            config.generators = only.clone();
+        }
+        if let Some(exclude) = &self.exclude {
+            config.exclude = exclude.clone();
+        }
+        if let Some(tag) = &self.tag {
+            config.tag = Some(tag.clone());
+        }
+        config.check_generators()?;
+        Ok(config)
+    }
+}
+
+/// Where the base seed comes from; at most one of these.
+#[derive(Args)]
+#[group(multiple = false)]
+pub struct SeedArgs {
+    /// Base seed in hex, from which every measurement's seed is derived
+    #[arg(long, value_name = "HEX")]
+    seed: Option<String>,
+    /// Read the base seed from the raw bytes of this file
+    #[arg(long, value_name = "FILE")]
+    seed_file: Option<PathBuf>,
+    /// Draw a fresh base seed from the OS entropy source
+    #[arg(long)]
+    os_entropy: bool,
+}
+
+impl SeedArgs {
+    /// The selected base seed, or `None` to keep the configured one.
+    pub fn seed(&self) -> Result<Option<Vec<u8>>, Box<dyn Error>> {
+        if let Some(hex) = &self.seed {
+            return Ok(Some(parse_hex(hex)?));
+        }
+        if let Some(path) = &self.seed_file {
+            let seed =
+                fs::read(path).map_err(|e| format!("cannot read {}: {e}", path.display()))?;
+            if seed.is_empty() {
+                return Err(format!("{} is empty", path.display()).into());
+            }
+            return Ok(Some(seed));
+        }
+        if self.os_entropy {
+            let seed = os_entropy_seed()?;
+            // The run cannot be repeated without it.
+            eprintln!(
+                "Seeded from OS entropy; repeat with --seed {}",
+                to_hex(&seed)
+            );
+            return Ok(Some(seed));
+        }
+        Ok(None)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Mode {
+    /// Full sweep with the statistical battery, plots and baselines
+    Sweep,
+    /// Multi-threaded throughput over 1, 2, 4, … threads
+    Scaling,
+    /// Per-call latency of 128- and 256-bit requests
+    Latency,
+    /// First request after cache eviction against the next one
+    ColdStart,
+    /// Log-normally sized request mix
+    Workload,
+    /// The battery and timings repeated over --seeds independent base seeds
+    SeedSweep,
+}
+
+#[derive(Args)]
+pub struct BenchArgs {
+    #[command(flatten)]
+    pub experiment: ExperimentArgs,
+    #[arg(long, value_enum, default_value_t = Mode::Sweep)]
+    pub mode: Mode,
+    /// Compare median times against this saved baseline and fail on regressions
+    #[arg(long, value_name = "NAME")]
+    pub baseline: Option<String>,
+    /// Save this run's summary as a baseline
+    #[arg(long, value_name = "NAME")]
+    pub save_baseline: Option<String>,
+    /// Slowdown in percent against --baseline that fails the run
+    #[arg(long, default_value_t = REGRESSION_THRESHOLD_PERCENT)]
+    pub regression_threshold: f64,
+    /// Pin the benchmark thread to this core (Linux only)
+    #[arg(long, value_name = "CORE")]
+    pub pin_core: Option<usize>,
+    /// Record instructions, cache misses and branch misses (Linux only)
+    #[arg(long)]
+    pub perf_counters: bool,
+    /// Measure in nested-loop order instead of the shuffled order
+    #[arg(long)]
+    pub no_shuffle: bool,
+    /// Pause before every measurement, in milliseconds
+    #[arg(long, value_name = "MS")]
+    pub cool_down_ms: Option<u64>,
+    /// Number of base seeds for --mode seed-sweep
+    #[arg(long, value_parser = parse_count, default_value_t = SEED_SWEEP_SEEDS)]
+    pub seeds: usize,
+    /// Median request size in bytes for --mode workload
+    #[arg(long, value_name = "BYTES", value_parser = parse_positive, default_value_t = DEFAULT_DISTRIBUTION.median_bytes)]
+    pub workload_median_bytes: f64,
+    /// Log-normal sigma of the request sizes for --mode workload
+    #[arg(long, value_name = "SIGMA", value_parser = parse_positive, default_value_t = DEFAULT_DISTRIBUTION.sigma)]
+    pub workload_sigma: f64,
+    /// Also write the per-run records to metrics.parquet
+    #[arg(long)]
+    pub parquet: bool,
+    /// Continue an interrupted sweep in the same output directory, measuring
+    /// only the (generator, length, run) cells its checkpoint lacks
+    #[arg(long)]
+    pub resume: bool,
+}
+
+impl BenchArgs {
+    /// The request-size distribution for --mode workload.
+    pub fn workload_distribution(&self) -> RequestSizes {
+        RequestSizes {
+            median_bytes: self.workload_median_bytes,
+            sigma: self.workload_sigma,
+        }
+    }
+}
+
+#[derive(Args)]
+pub struct GenerateArgs {
+    /// Generator name or slug, as printed by list-generators; a trailing
+    /// _drbg may be left off
+    #[arg(long)]
+    pub generator: String,
+    /// Number of bytes to write, with an optional K, M, G or T suffix
+    #[arg(long, value_parser = parse_size, required_unless_present = "stream")]
+    pub bytes: Option<usize>,
+    /// Output file; stdout if omitted
+    #[arg(long)]
+    pub out: Option<PathBuf>,
+    /// Write to stdout until the reader closes it, for dieharder or PractRand
+    #[arg(long, conflicts_with_all = ["bytes", "out"])]
+    pub stream: bool,
+    #[command(flatten)]
+    pub seed: SeedArgs,
+}
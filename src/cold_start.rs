@@ -0,0 +1,104 @@
+//! Cold-start benchmark: the first request served by a freshly built
+//! generator after the CPU caches were evicted, against the next request of
+//! the same size, to separate first-use latency from steady-state cost.
+
+use crate::config::Config;
+use crate::latency::quantile;
+use std::error::Error;
+use std::fs::File;
+use std::hint::black_box;
+use std::io::Write;
+use std::path::Path;
+use std::time::Instant;
+
+const REQUEST_BITS: [usize; 2] = [1_024, 100_000];
+const RUNS: usize = 20;
+// Written through before every cold request; larger than the last-level
+// cache of current desktop and server parts.
+const EVICTION_BYTES: usize = 64 << 20;
+// Stride of the eviction writes, one cache line.
+const CACHE_LINE_BYTES: usize = 64;
+
+pub struct ColdStartSample {
+    pub generator: String,
+    pub cryptographic: bool,
+    pub request_bits: usize,
+    /// First request after instantiation and eviction, in nanoseconds, sorted.
+    pub cold_ns: Vec<u64>,
+    /// Second request of the same instance, in nanoseconds, sorted.
+    pub warm_ns: Vec<u64>,
+}
+
+/// Pushes the generator's code, tables and state out of the data caches by
+/// dirtying every line of `buffer`. Instruction caches and branch predictors
+/// are only disturbed indirectly.
+fn evict(buffer: &mut [u8]) {
+    for line in buffer.chunks_mut(CACHE_LINE_BYTES) {
+        line[0] = line[0].wrapping_add(1);
+    }
+    black_box(buffer);
+}
+
+pub fn run(config: &Config) -> Vec<ColdStartSample> {
+    let mut buffer = vec![0u8; EVICTION_BYTES];
+    let mut samples = Vec::new();
+    for &request_bits in REQUEST_BITS.iter() {
+        for new in config.constructors() {
+            let mut sample: Option<ColdStartSample> = None;
+            for run in 0..RUNS {
+                let Some(mut drbg) = new(&config.seed_for(run, request_bits)) else {
+                    break;
+                };
+                evict(&mut buffer);
+                let start = Instant::now();
+                drbg.generate_bits(request_bits);
+                let cold = start.elapsed().as_nanos() as u64;
+                let start = Instant::now();
+                drbg.generate_bits(request_bits);
+                let warm = start.elapsed().as_nanos() as u64;
+
+                let sample = sample.get_or_insert_with(|| ColdStartSample {
+                    generator: drbg.name().to_string(),
+                    cryptographic: drbg.is_cryptographic(),
+                    request_bits,
+                    cold_ns: Vec::with_capacity(RUNS),
+                    warm_ns: Vec::with_capacity(RUNS),
+                });
+                sample.cold_ns.push(cold);
+                sample.warm_ns.push(warm);
+            }
+            if let Some(mut sample) = sample {
+                sample.cold_ns.sort_unstable();
+                sample.warm_ns.sort_unstable();
+                samples.push(sample);
+            }
+        }
+    }
+    samples
+}
+
+pub fn write_csv(dir: &Path, samples: &[ColdStartSample]) -> Result<(), Box<dyn Error>> {
+    let mut file = File::create(dir.join("cold_start.csv"))?;
+    writeln!(
+        file,
+        "generator,cryptographic,request_bits,runs,median_cold_ns,max_cold_ns,median_warm_ns,max_warm_ns,cold_warm_ratio"
+    )?;
+    for s in samples {
+        let cold = quantile(&s.cold_ns, 0.5);
+        let warm = quantile(&s.warm_ns, 0.5);
+        writeln!(
+            file,
+            "{},{},{},{},{},{},{},{},{:.2}",
+            s.generator,
+            s.cryptographic,
+            s.request_bits,
+            s.cold_ns.len(),
+            cold,
+            s.cold_ns.last().copied().unwrap_or_default(),
+            warm,
+            s.warm_ns.last().copied().unwrap_or_default(),
+            cold as f64 / warm.max(1) as f64
+        )?;
+    }
+    Ok(())
+}
@@ -0,0 +1,319 @@
+//! Benchmark parameters that can change between runs without recompiling:
+//! the target lengths, the number of runs and warm-up runs, the outlier
+//! cut-off, the base seed, which generators and tests take part, where
+//! results go and how the sweep is labelled. They
+//! come from the defaults below, an optional TOML experiment file, and
+//! command-line flags, each overriding the previous.
+
+use random_numbers::drbg::{Constructor, Drbg, build_generators, constructors};
+use random_numbers::{DEFAULT_LENGTHS, DEFAULT_SEED};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const DEFAULT_RUNS: usize = 50;
+const DEFAULT_OUTPUT_DIR: &str = "results";
+const DEFAULT_WARMUP_RUNS: usize = 3;
+const DEFAULT_OUTLIER_MADS: f64 = 3.0;
+// Length of a base seed drawn from the OS, matching the 256-bit security
+// strength of the strongest generators.
+const OS_SEED_BYTES: usize = 32;
+
+#[derive(Clone)]
+pub struct Config {
+    /// Output lengths in bits, ascending and distinct.
+    pub lengths: Vec<usize>,
+    /// Measured repetitions of every (generator, length) configuration.
+    pub runs: usize,
+    /// Untimed generations per (generator, length) before the measured runs.
+    pub warmup_runs: usize,
+    /// Timings further than this many scaled MADs from their group's median
+    /// are flagged as outliers and left out of the timing summaries.
+    pub outlier_mads: f64,
+    /// Every seed in the run is derived from this; see [`Config::seed_for`].
+    pub seed: Vec<u8>,
+    /// Generator names or file slugs to include; empty for all.
+    pub generators: Vec<String>,
+    /// Generator names or file slugs to leave out, applied after `generators`.
+    pub exclude: Vec<String>,
+    pub tests: Tests,
+    pub output_dir: PathBuf,
+    /// Label stored with every record and summary, such as
+    /// `before-optimization`; see [`parse_tag`].
+    pub tag: Option<String>,
+}
+
+/// Optional parts of the statistical analysis; all enabled by default.
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Tests {
+    /// SP 800-22 battery on every measured sequence.
+    pub sts: bool,
+    /// Extended battery (chi-square, poker, autocorrelation, birthday
+    /// spacings, gap, overlapping permutations) on every measured sequence.
+    pub extended: bool,
+    /// SP 800-90B min-entropy estimators on every measured sequence.
+    pub min_entropy: bool,
+    /// Seed avalanche analysis.
+    pub avalanche: bool,
+    /// Bit autocorrelation plots over lags.
+    pub lag_spectra: bool,
+}
+
+impl Default for Tests {
+    fn default() -> Tests {
+        Tests {
+            sts: true,
+            extended: true,
+            min_entropy: true,
+            avalanche: true,
+            lag_spectra: true,
+        }
+    }
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config {
+            lengths: DEFAULT_LENGTHS.to_vec(),
+            runs: DEFAULT_RUNS,
+            warmup_runs: DEFAULT_WARMUP_RUNS,
+            outlier_mads: DEFAULT_OUTLIER_MADS,
+            seed: DEFAULT_SEED.to_vec(),
+            generators: Vec::new(),
+            exclude: Vec::new(),
+            tests: Tests::default(),
+            output_dir: PathBuf::from(DEFAULT_OUTPUT_DIR),
+            tag: None,
+        }
+    }
+}
+
+/// Layout of an experiment file; every key is optional.
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ExperimentFile {
+    lengths: Option<Vec<usize>>,
+    runs: Option<usize>,
+    warmup_runs: Option<usize>,
+    outlier_mads: Option<f64>,
+    seed: Option<String>,
+    generators: Option<Vec<String>>,
+    exclude: Option<Vec<String>>,
+    tests: Option<Tests>,
+    output_dir: Option<PathBuf>,
+    tag: Option<String>,
+}
+
+/// Parses a positive count such as `1000000` or `1_000_000`.
+pub fn parse_count(value: &str) -> Result<usize, String> {
+    let count: usize = value
+        .replace('_', "")
+        .parse()
+        .map_err(|e| format!("invalid count {value:?}: {e}"))?;
+    if count == 0 {
+        return Err(format!("invalid count {value:?}: must be positive"));
+    }
+    Ok(count)
+}
+
+/// Parses a positive, finite number such as `3` or `2.5`.
+pub fn parse_positive(value: &str) -> Result<f64, String> {
+    let number: f64 = value
+        .parse()
+        .map_err(|e| format!("invalid number {value:?}: {e}"))?;
+    if !(number.is_finite() && number > 0.0) {
+        return Err(format!("invalid number {value:?}: must be positive"));
+    }
+    Ok(number)
+}
+
+/// Parses a sweep tag: any non-empty text without commas, quotes or control
+/// characters, so it can go into the CSV files unquoted.
+pub fn parse_tag(value: &str) -> Result<String, String> {
+    if value.is_empty()
+        || value
+            .chars()
+            .any(|c| c == ',' || c == '"' || c.is_control())
+    {
+        return Err(format!(
+            "invalid tag {value:?}: needs to be non-empty, without commas, quotes or control characters"
+        ));
+    }
+    Ok(value.to_string())
+}
+
+/// Whether `pattern` names the generator `name`: verbatim, as its file slug,
+/// or as the slug without a trailing `_drbg` (`chacha20` for ChaCha20 DRBG).
+pub fn matches(pattern: &str, name: &str) -> bool {
+    let slug = crate::file_slug(name);
+    pattern == name || pattern == slug || slug.strip_suffix("_drbg") == Some(pattern)
+}
+
+/// Parses a positive byte count with an optional binary suffix: `4096`,
+/// `64K`, `10M`, `1G` or `2T`.
+pub fn parse_size(value: &str) -> Result<usize, String> {
+    let (digits, shift) = match value.char_indices().last() {
+        Some((i, c)) if c.is_ascii_alphabetic() => {
+            let shift = match c.to_ascii_uppercase() {
+                'K' => 10,
+                'M' => 20,
+                'G' => 30,
+                'T' => 40,
+                _ => return Err(format!("invalid size {value:?}: unknown suffix {c:?}")),
+            };
+            (&value[..i], shift)
+        }
+        _ => (value, 0),
+    };
+    let count = parse_count(digits).map_err(|_| format!("invalid size {value:?}"))?;
+    count
+        .checked_mul(1 << shift)
+        .ok_or_else(|| format!("invalid size {value:?}: too large"))
+}
+
+/// Parses a seed given as hex digits, with or without a `0x` prefix.
+pub fn parse_hex(value: &str) -> Result<Vec<u8>, String> {
+    let digits = value.strip_prefix("0x").unwrap_or(value);
+    if digits.is_empty() || !digits.len().is_multiple_of(2) {
+        return Err(format!(
+            "invalid hex seed {value:?}: needs a positive, even number of digits"
+        ));
+    }
+    if !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(format!("invalid hex seed {value:?}: not a hex digit"));
+    }
+    Ok((0..digits.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&digits[i..i + 2], 16).expect("checked hex digits"))
+        .collect())
+}
+
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// A fresh base seed from the OS entropy source, for runs that should see
+/// realistic seeding rather than a reproducible one.
+pub fn os_entropy_seed() -> Result<Vec<u8>, String> {
+    let mut seed = vec![0u8; OS_SEED_BYTES];
+    getrandom::getrandom(&mut seed).map_err(|e| format!("cannot read OS entropy: {e}"))?;
+    Ok(seed)
+}
+
+impl Config {
+    /// The defaults, overridden by the keys present in the TOML file at `path`.
+    pub fn load(path: &Path) -> Result<Config, Box<dyn Error>> {
+        let contents =
+            fs::read_to_string(path).map_err(|e| format!("cannot read {}: {e}", path.display()))?;
+        let file: ExperimentFile =
+            toml::from_str(&contents).map_err(|e| format!("{}: {e}", path.display()))?;
+
+        let mut config = Config::default();
+        if let Some(lengths) = file.lengths {
+            config.set_lengths(lengths);
+        }
+        if let Some(runs) = file.runs {
+            config.runs = runs;
+        }
+        if let Some(warmup_runs) = file.warmup_runs {
+            config.warmup_runs = warmup_runs;
+        }
+        if let Some(outlier_mads) = file.outlier_mads {
+            config.outlier_mads = outlier_mads;
+        }
+        if let Some(seed) = file.seed {
+            config.seed = parse_hex(&seed).map_err(|e| format!("{}: {e}", path.display()))?;
+        }
+        if let Some(generators) = file.generators {
+            config.generators = generators;
+        }
+        if let Some(exclude) = file.exclude {
+            config.exclude = exclude;
+        }
+        if let Some(tests) = file.tests {
+            config.tests = tests;
+        }
+        if let Some(output_dir) = file.output_dir {
+            config.output_dir = output_dir;
+        }
+        if let Some(tag) = file.tag {
+            config.tag = Some(parse_tag(&tag).map_err(|e| format!("{}: {e}", path.display()))?);
+        }
+        if config.lengths.contains(&0) || config.runs == 0 {
+            return Err(format!("{}: lengths and runs must be positive", path.display()).into());
+        }
+        if !(config.outlier_mads.is_finite() && config.outlier_mads > 0.0) {
+            return Err(format!("{}: outlier_mads must be positive", path.display()).into());
+        }
+        config
+            .check_generators()
+            .map_err(|e| format!("{}: {e}", path.display()))?;
+        Ok(config)
+    }
+
+    /// Fails on generator names that match no generator, which would
+    /// otherwise silently drop out of the experiment.
+    pub fn check_generators(&self) -> Result<(), String> {
+        let known = build_generators(&self.seed);
+        for pattern in self.generators.iter().chain(&self.exclude) {
+            if !known.iter().any(|d| matches(pattern, d.name())) {
+                return Err(format!("unknown generator {pattern:?}"));
+            }
+        }
+        if known.iter().all(|d| !self.includes(d.name())) {
+            return Err("every generator is filtered out".to_string());
+        }
+        Ok(())
+    }
+
+    pub fn set_lengths(&mut self, mut lengths: Vec<usize>) {
+        lengths.sort_unstable();
+        lengths.dedup();
+        self.lengths = lengths;
+    }
+
+    /// Seed for one measurement: the base seed followed by `run` and `bits`
+    /// as big-endian u64s, so every configuration gets a distinct stream.
+    pub fn seed_for(&self, run: usize, bits: usize) -> Vec<u8> {
+        let mut seed = Vec::with_capacity(self.seed.len() + 16);
+        seed.extend_from_slice(&self.seed);
+        seed.extend_from_slice(&(run as u64).to_be_bytes());
+        seed.extend_from_slice(&(bits as u64).to_be_bytes());
+        seed
+    }
+
+    /// Base seed `index` of a seed sweep: the SHA-256 of the base seed
+    /// followed by `index` as a big-endian u64. Each one starts its own chain
+    /// of [`Config::seed_for`] seeds.
+    pub fn sweep_seed(&self, index: usize) -> Vec<u8> {
+        let mut hasher = Sha256::new();
+        hasher.update(&self.seed);
+        hasher.update((index as u64).to_be_bytes());
+        hasher.finalize().to_vec()
+    }
+
+    /// Whether the generator called `name` takes part.
+    pub fn includes(&self, name: &str) -> bool {
+        (self.generators.is_empty() || self.generators.iter().any(|g| matches(g, name)))
+            && !self.exclude.iter().any(|g| matches(g, name))
+    }
+
+    /// The included generators, seeded with `seed`.
+    pub fn build_generators(&self, seed: &[u8]) -> Vec<Box<dyn Drbg>> {
+        build_generators(seed)
+            .into_iter()
+            .filter(|drbg| self.includes(drbg.name()))
+            .collect()
+    }
+
+    /// Constructors of the included generators that are available here.
+    pub fn constructors(&self) -> Vec<Constructor> {
+        constructors()
+            .into_iter()
+            .filter(|new| new(&self.seed).is_some_and(|drbg| self.includes(drbg.name())))
+            .collect()
+    }
+}
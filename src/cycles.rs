@@ -0,0 +1,29 @@
+//! Cycle counters for the timing loop. On x86-64 this is the time-stamp
+//! counter, which ticks at a constant reference rate rather than the current
+//! core clock; on aarch64 the virtual counter `cntvct_el0`, whose frequency is
+//! fixed by the platform (often well below the core clock). Elsewhere no
+//! counter is available.
+
+#[cfg(target_arch = "x86_64")]
+pub fn read() -> Option<u64> {
+    use std::arch::x86_64::{_mm_lfence, _rdtsc};
+    // The fence keeps earlier instructions from drifting past the read.
+    unsafe {
+        _mm_lfence();
+        Some(_rdtsc())
+    }
+}
+
+#[cfg(target_arch = "aarch64")]
+pub fn read() -> Option<u64> {
+    let value: u64;
+    unsafe {
+        std::arch::asm!("isb", "mrs {}, cntvct_el0", out(reg) value, options(nomem, nostack));
+    }
+    Some(value)
+}
+
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+pub fn read() -> Option<u64> {
+    None
+}
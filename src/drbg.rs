@@ -1,19 +1,129 @@
-use aes::Aes256;
+use aes::cipher::consts::{U16, U32};
+use aes::cipher::{BlockEncrypt, BlockSizeUser, KeyInit, KeySizeUser};
+use aes::{Aes128, Aes256};
+use ascon_hash::AsconXof;
+use blake2::Blake2bMac512;
 use blake3::Hasher;
+use camellia::Camellia256;
+use chacha20::{ChaCha20, ChaCha20Legacy};
 use core::convert::TryInto;
 use ctr::cipher::generic_array::GenericArray;
 use ctr::cipher::{KeyIvInit, StreamCipher};
-use rand_chacha::ChaCha20Rng;
+use des::TdesEde3;
+use hc_256::Hc256;
+use hmac::{Hmac, Mac};
+use p256::elliptic_curve::ops::Reduce;
+use p256::elliptic_curve::point::AffineCoordinates;
+use p256::elliptic_curve::sec1::FromEncodedPoint;
+use p256::{AffinePoint, EncodedPoint, ProjectivePoint, Scalar, U256};
+use rabbit::Rabbit;
+use rand_chacha::{ChaCha8Rng, ChaCha12Rng, ChaCha20Rng};
 use rand_core::{RngCore, SeedableRng};
+use rand_isaac::IsaacRng;
+use rand_mt::Mt64;
+use rand_pcg::Pcg64;
+use rand_xoshiro::{SplitMix64, Xoshiro256StarStar};
+use rayon::prelude::*;
+use rc4::Rc4;
+use sha2::{Digest, Sha256, Sha512};
+use sha3::digest::{ExtendableOutput, XofReader};
+use sha3::{CShake256, CShake256Core, Sha3_256, Shake128, Shake256};
+use sm4::Sm4;
 use std::io::Read;
+use std::iter;
 
 const AES_BLOCK_BYTES: usize = 16;
+const BLAKE2B_OUT_BYTES: usize = 64;
+// Dual_EC_DRBG on P-256: seedlen 256 bits, outlen 240 bits (SP 800-90A Table 4).
+const DUAL_EC_SEED_BYTES: usize = 32;
+const DUAL_EC_OUT_BYTES: usize = 30;
+const DUAL_EC_QX: [u8; 32] = [
+    0xc9, 0x74, 0x45, 0xf4, 0x5c, 0xde, 0xf9, 0xf0, 0xd3, 0xe0, 0x5e, 0x1e, 0x58, 0x5f, 0xc2, 0x97,
+    0x23, 0x5b, 0x82, 0xb5, 0xbe, 0x8f, 0xf3, 0xef, 0xca, 0x67, 0xc5, 0x98, 0x52, 0x01, 0x81, 0x92,
+];
+const DUAL_EC_QY: [u8; 32] = [
+    0xb2, 0x8e, 0xf5, 0x57, 0xba, 0x31, 0xdf, 0xcb, 0xdd, 0x21, 0xac, 0x46, 0xe2, 0xa9, 0x1e, 0x3c,
+    0x30, 0x4f, 0x44, 0xcb, 0x87, 0x05, 0x8a, 0xda, 0x2c, 0xb8, 0x15, 0x15, 0x1e, 0x61, 0x00, 0x46,
+];
+const FORTUNA_POOLS: usize = 32;
+const FORTUNA_MIN_POOL_BYTES: usize = 64;
+const FORTUNA_EVENT_BYTES: usize = 32;
+// Fortuna caps a single request at 2^20 bytes before the generator rekeys.
+const FORTUNA_MAX_REQUEST_BYTES: usize = 1 << 20;
+// Keccak-f[1600] rate with a 512-bit capacity (1088 bits), as in SHA3-256/SHAKE256.
+const KECCAK_DUPLEX_RATE_BYTES: usize = 136;
+// cSHAKE256 rate (1088 bits), used as the bytepad width for KMAC256.
+const KMAC256_RATE_BYTES: usize = 136;
+const KMAC_DRBG_CUSTOMIZATION: &[u8] = b"DRBG performance analysis";
+// Trivium uses an 80-bit key and an 80-bit IV.
+const TRIVIUM_KEY_BYTES: usize = 10;
+const GRAIN_NONCE_BYTES: usize = 12;
+// The 128-bit multiplier and increment used by PCG's underlying LCG.
+const LCG128_MULTIPLIER: u128 = 0x2360_ED05_1FC6_5DA4_4385_DF64_9FCC_F645;
+const LCG128_INCREMENT: u128 = 0x5851_F42D_4C95_7F2D_1405_7B7E_F767_814F;
+const WYRAND_INCREMENT: u64 = 0xA076_1D64_78BD_642F;
+const WYRAND_MIX: u64 = 0xE703_7ED1_A0B4_28DB;
+const ROMU_TRIO_MULTIPLIER: u64 = 0xD383_3E80_4F4C_574B;
+const PHILOX_ROUNDS: usize = 10;
+const PHILOX_M0: u32 = 0xD251_1F53;
+const PHILOX_M1: u32 = 0xCD9E_8D57;
+const PHILOX_W0: u32 = 0x9E37_79B9;
+const PHILOX_W1: u32 = 0xBB67_AE85;
+const THREEFRY_ROUNDS: usize = 20;
+const THREEFRY_PARITY: u64 = 0x1BD1_1BDA_A9FC_1A22;
+const THREEFRY_ROTATIONS: [[u32; 2]; 8] = [
+    [14, 16],
+    [52, 57],
+    [23, 40],
+    [5, 37],
+    [25, 33],
+    [46, 12],
+    [58, 22],
+    [32, 32],
+];
+const SPECK_ROUNDS: usize = 34;
+const SIMON_ROUNDS: usize = 72;
+// Simon's z4 round-constant sequence, bit j holding z4[j].
+const SIMON_Z4: u64 = 0b0011110111001001010011000011101000000100011011010110011110001011;
+const SHA256_OUT_BYTES: usize = 32;
+// XDRBG-256: a 512-bit state and at most 2^16 output bytes per XOF call.
+const XDRBG_STATE_BYTES: usize = 64;
+const XDRBG_MAX_REQUEST_BYTES: usize = 1 << 16;
+// SP 800-90A Table 2: at most 2^19 bits per generate request.
+const SP800_90A_MAX_REQUEST_BYTES: usize = (1 << 19) / 8;
+// Unit of work in the parallel fills; divides SP800_90A_MAX_REQUEST_BYTES and
+// is a whole number of AES and BLAKE3 blocks.
+const PARALLEL_CHUNK_BYTES: usize = 16 * 1024;
 
 #[allow(dead_code)]
 pub trait Drbg {
     fn name(&self) -> &'static str;
+    /// Bits of secret key or seed state set at instantiation; `None` for
+    /// sources that keep no state of their own (the OS and the CPU).
+    fn key_bits(&self) -> Option<usize>;
+    /// Bits produced per call of the underlying primitive: a cipher or hash
+    /// block, an XOF rate, or a PRNG output word.
+    fn block_bits(&self) -> Option<usize>;
+    /// Security strength in bits claimed for the construction; `None` for
+    /// non-cryptographic generators and designs without an established claim.
+    fn security_strength(&self) -> Option<usize>;
+    /// `false` for statistical PRNGs included only as performance baselines and
+    /// for broken historical designs.
+    fn is_cryptographic(&self) -> bool {
+        true
+    }
     fn reseed(&mut self, seed: &[u8]);
     fn generate_bits(&mut self, bits: usize) -> BitString;
+    /// Whether `generate_bits_parallel` has a parallel path, so callers can
+    /// ask without generating (and advancing the state).
+    fn supports_parallel(&self) -> bool {
+        false
+    }
+    /// Same output as `generate_bits`, filled on the rayon thread pool by
+    /// splitting the counter space; `None` for generators without that path.
+    fn generate_bits_parallel(&mut self, _bits: usize) -> Option<BitString> {
+        None
+    }
 }
 
 #[derive(Clone)]
@@ -33,6 +143,11 @@ impl BitString {
         self.bytes.len()
     }
 
+    /// Bit `index` of the string, most significant bit of each byte first.
+    pub fn bit(&self, index: usize) -> u8 {
+        (self.bytes[index / 8] >> (7 - index % 8)) & 1
+    }
+
     pub fn count_bits(&self) -> BitTally {
         let full_bytes = self.bits / 8;
         let remainder = self.bits % 8;
@@ -78,114 +193,2684 @@ fn derive_seed(seed: &[u8], context: &str) -> [u8; 32] {
     out
 }
 
-pub struct ChaCha20Drbg {
-    rng: ChaCha20Rng,
+/// Instantiates a `rand_core` RNG from a BLAKE3 expansion sized to its native seed.
+fn seedable_rng_from_seed<R: SeedableRng>(seed: &[u8], context: &str) -> R {
+    let mut native = R::Seed::default();
+    let material = derive_material(seed, context, native.as_mut().len());
+    native.as_mut().copy_from_slice(&material);
+    R::from_seed(native)
+}
+
+/// Declares a DRBG wrapping a `rand_core` RNG; all of them share the same BLAKE3
+/// seed derivation, so the ChaCha round variants differ only in the round count.
+macro_rules! seedable_rng_drbg {
+    (
+        $drbg:ident,
+        $rng:ty,
+        $name:literal,
+        $context:literal,
+        $cryptographic:literal,
+        $block_bits:expr,
+        $strength:expr
+    ) => {
+        pub struct $drbg {
+            rng: $rng,
+        }
+
+        impl $drbg {
+            pub fn new(seed: &[u8]) -> Self {
+                Self {
+                    rng: seedable_rng_from_seed(seed, $context),
+                }
+            }
+        }
+
+        impl Drbg for $drbg {
+            fn name(&self) -> &'static str {
+                $name
+            }
+
+            fn is_cryptographic(&self) -> bool {
+                $cryptographic
+            }
+
+            fn key_bits(&self) -> Option<usize> {
+                Some(8 * size_of::<<$rng as SeedableRng>::Seed>())
+            }
+
+            fn block_bits(&self) -> Option<usize> {
+                Some($block_bits)
+            }
+
+            fn security_strength(&self) -> Option<usize> {
+                $strength
+            }
+
+            fn reseed(&mut self, seed: &[u8]) {
+                self.rng = seedable_rng_from_seed(seed, $context);
+            }
+
+            fn generate_bits(&mut self, bits: usize) -> BitString {
+                let byte_len = bits.div_ceil(8);
+                let mut bytes = vec![0u8; byte_len];
+                self.rng.fill_bytes(&mut bytes);
+                BitString { bits, bytes }
+            }
+        }
+    };
+}
+
+seedable_rng_drbg!(
+    ChaCha20Drbg,
+    ChaCha20Rng,
+    "ChaCha20 DRBG",
+    "chacha20-drbg",
+    true,
+    512,
+    Some(256)
+);
+seedable_rng_drbg!(
+    ChaCha12Drbg,
+    ChaCha12Rng,
+    "ChaCha12 DRBG",
+    "chacha12-drbg",
+    true,
+    512,
+    Some(256)
+);
+seedable_rng_drbg!(
+    ChaCha8Drbg,
+    ChaCha8Rng,
+    "ChaCha8 DRBG",
+    "chacha8-drbg",
+    true,
+    512,
+    Some(256)
+);
+seedable_rng_drbg!(
+    IsaacDrbg,
+    IsaacRng,
+    "ISAAC DRBG",
+    "isaac-drbg",
+    true,
+    8192,
+    None
+);
+seedable_rng_drbg!(
+    Xoshiro256StarStarDrbg,
+    Xoshiro256StarStar,
+    "xoshiro256**",
+    "xoshiro256-drbg",
+    false,
+    64,
+    None
+);
+seedable_rng_drbg!(Pcg64Drbg, Pcg64, "PCG64", "pcg64-drbg", false, 64, None);
+seedable_rng_drbg!(
+    Mt19937Drbg,
+    Mt64,
+    "MT19937-64",
+    "mt19937-64-drbg",
+    false,
+    64,
+    None
+);
+seedable_rng_drbg!(
+    SplitMix64Drbg,
+    SplitMix64,
+    "SplitMix64",
+    "splitmix64-drbg",
+    false,
+    64,
+    None
+);
+
+/// Truncated 128-bit LCG: x <- a*x + c mod 2^128, emitting the high 64 bits.
+/// It is the simplest generator here and bounds how fast a buffer can be filled.
+pub struct Lcg128Drbg {
+    state: u128,
 }
 
-impl ChaCha20Drbg {
+impl Lcg128Drbg {
     pub fn new(seed: &[u8]) -> Self {
-        let derived = derive_seed(seed, "chacha20-drbg");
+        let material = derive_material(seed, "lcg128-drbg", 16);
         Self {
-            rng: ChaCha20Rng::from_seed(derived),
+            state: u128::from_le_bytes(material.try_into().unwrap()),
         }
     }
 }
 
-impl Drbg for ChaCha20Drbg {
+impl Drbg for Lcg128Drbg {
     fn name(&self) -> &'static str {
-        "ChaCha20 DRBG"
+        "LCG128"
+    }
+
+    fn key_bits(&self) -> Option<usize> {
+        Some(128)
+    }
+
+    fn block_bits(&self) -> Option<usize> {
+        Some(64)
+    }
+
+    fn security_strength(&self) -> Option<usize> {
+        None
+    }
+
+    fn is_cryptographic(&self) -> bool {
+        false
     }
 
     fn reseed(&mut self, seed: &[u8]) {
-        let derived = derive_seed(seed, "chacha20-drbg");
-        self.rng = ChaCha20Rng::from_seed(derived);
+        *self = Self::new(seed);
     }
 
     fn generate_bits(&mut self, bits: usize) -> BitString {
-        let byte_len = (bits + 7) / 8;
+        let byte_len = bits.div_ceil(8);
         let mut bytes = vec![0u8; byte_len];
-        self.rng.fill_bytes(&mut bytes);
+        for chunk in bytes.chunks_mut(8) {
+            self.state = self
+                .state
+                .wrapping_mul(LCG128_MULTIPLIER)
+                .wrapping_add(LCG128_INCREMENT);
+            let out = ((self.state >> 64) as u64).to_le_bytes();
+            chunk.copy_from_slice(&out[..chunk.len()]);
+        }
         BitString { bits, bytes }
     }
 }
 
-type Aes256Ctr = ctr::Ctr128BE<Aes256>;
+/// Derives a fresh key and IV for a `cipher` stream cipher from one BLAKE3 expansion.
+fn stream_cipher_from_seed<C: KeyIvInit>(seed: &[u8], context: &str) -> C {
+    let material = derive_material(seed, context, C::key_size() + C::iv_size());
+    let (key, iv) = material.split_at(C::key_size());
+    C::new(GenericArray::from_slice(key), GenericArray::from_slice(iv))
+}
+
+/// Declares a DRBG that emits the raw keystream of a `cipher` stream cipher,
+/// keyed and IV'd from the seed; the stream position carries over between requests.
+macro_rules! stream_cipher_drbg {
+    (
+        $drbg:ident,
+        $cipher:ty,
+        $name:literal,
+        $context:literal,
+        $key_bits:expr,
+        $block_bits:expr,
+        $strength:expr
+    ) => {
+        pub struct $drbg {
+            cipher: $cipher,
+        }
 
-pub struct AesCtrDrbg {
-    key: GenericArray<u8, <Aes256 as aes::cipher::KeySizeUser>::KeySize>,
-    counter: u128,
+        impl $drbg {
+            pub fn new(seed: &[u8]) -> Self {
+                Self {
+                    cipher: stream_cipher_from_seed(seed, $context),
+                }
+            }
+        }
+
+        impl Drbg for $drbg {
+            fn name(&self) -> &'static str {
+                $name
+            }
+
+            fn key_bits(&self) -> Option<usize> {
+                Some($key_bits)
+            }
+
+            fn block_bits(&self) -> Option<usize> {
+                Some($block_bits)
+            }
+
+            fn security_strength(&self) -> Option<usize> {
+                $strength
+            }
+
+            fn reseed(&mut self, seed: &[u8]) {
+                self.cipher = stream_cipher_from_seed(seed, $context);
+            }
+
+            fn generate_bits(&mut self, bits: usize) -> BitString {
+                let byte_len = bits.div_ceil(8);
+                let mut bytes = vec![0u8; byte_len];
+                self.cipher.apply_keystream(&mut bytes);
+                BitString { bits, bytes }
+            }
+        }
+    };
+}
+
+stream_cipher_drbg!(
+    Hc256Drbg,
+    Hc256,
+    "HC-256 DRBG",
+    "hc256-drbg",
+    256,
+    32,
+    Some(256)
+);
+stream_cipher_drbg!(
+    RabbitDrbg,
+    Rabbit,
+    "Rabbit DRBG",
+    "rabbit-drbg",
+    128,
+    128,
+    Some(128)
+);
+// RFC 8439 ChaCha20 (96-bit nonce, 32-bit block counter, so 256 GiB per
+// nonce) against Bernstein's original layout (64-bit nonce, 64-bit counter).
+stream_cipher_drbg!(
+    ChaCha20IetfDrbg,
+    ChaCha20,
+    "ChaCha20 (IETF)",
+    "chacha20-ietf-drbg",
+    256,
+    512,
+    Some(256)
+);
+stream_cipher_drbg!(
+    ChaCha20LegacyDrbg,
+    ChaCha20Legacy,
+    "ChaCha20 (original nonce)",
+    "chacha20-legacy-drbg",
+    256,
+    512,
+    Some(256)
+);
+stream_cipher_drbg!(
+    CamelliaCtrDrbg,
+    ctr::Ctr128BE<Camellia256>,
+    "Camellia-256-CTR",
+    "camellia-ctr-drbg",
+    256,
+    128,
+    Some(256)
+);
+// Three-key EDE Triple-DES with its 64-bit block, so the counter is 64 bits too.
+stream_cipher_drbg!(
+    TdesCtrDrbg,
+    ctr::Ctr64BE<TdesEde3>,
+    "3DES-CTR (legacy)",
+    "tdes-ctr-drbg",
+    168,
+    64,
+    Some(112)
+);
+
+/// Dual_EC_DRBG over P-256 as specified in NIST SP 800-90A (2012), Appendix A.1,
+/// with the standard P and Q. It is insecure by design (whoever knows e with
+/// P = eQ can predict the output), was withdrawn in 2014, and is included purely
+/// as a pedagogical data point for elliptic-curve generation cost.
+pub struct DualEcDrbg {
+    s: [u8; DUAL_EC_SEED_BYTES],
+    q: ProjectivePoint,
+    reseed_counter: u64,
 }
 
-impl AesCtrDrbg {
+impl DualEcDrbg {
     pub fn new(seed: &[u8]) -> Self {
-        let material = derive_material(seed, "aes-ctr-drbg", 48);
-        let mut key = GenericArray::default();
-        key.copy_from_slice(&material[..32]);
-        let counter = u128::from_be_bytes(material[32..48].try_into().unwrap());
-        Self { key, counter }
+        let material = derive_material(seed, "dual-ec-drbg", 48);
+        let mut s = [0u8; DUAL_EC_SEED_BYTES];
+        hash_df::<Sha256>(&[&material], &mut s);
+        let q =
+            EncodedPoint::from_affine_coordinates(&DUAL_EC_QX.into(), &DUAL_EC_QY.into(), false);
+        Self {
+            s,
+            q: AffinePoint::from_encoded_point(&q)
+                .expect("the SP 800-90A Q point lies on P-256")
+                .into(),
+            reseed_counter: 1,
+        }
+    }
+
+    /// x(scalar * point), with the x-coordinate as a big-endian 32-byte string.
+    fn x_of_multiple(point: ProjectivePoint, scalar: &[u8; DUAL_EC_SEED_BYTES]) -> [u8; 32] {
+        let scalar = <Scalar as Reduce<U256>>::reduce_bytes(&(*scalar).into());
+        (point * scalar).to_affine().x().into()
     }
 }
 
-impl Drbg for AesCtrDrbg {
+impl Drbg for DualEcDrbg {
     fn name(&self) -> &'static str {
-        "AES-256-CTR DRBG"
+        "Dual_EC_DRBG (P-256)"
+    }
+
+    fn key_bits(&self) -> Option<usize> {
+        Some(8 * DUAL_EC_SEED_BYTES)
+    }
+
+    fn block_bits(&self) -> Option<usize> {
+        Some(8 * DUAL_EC_OUT_BYTES)
+    }
+
+    fn security_strength(&self) -> Option<usize> {
+        None
+    }
+
+    fn is_cryptographic(&self) -> bool {
+        false
     }
 
     fn reseed(&mut self, seed: &[u8]) {
-        let material = derive_material(seed, "aes-ctr-drbg", 48);
-        self.key.copy_from_slice(&material[..32]);
-        self.counter = u128::from_be_bytes(material[32..48].try_into().unwrap());
+        let entropy = derive_material(seed, "dual-ec-drbg", 32);
+        let previous = self.s;
+        hash_df::<Sha256>(&[&previous, &entropy], &mut self.s);
+        self.reseed_counter = 1;
     }
 
     fn generate_bits(&mut self, bits: usize) -> BitString {
-        let byte_len = (bits + 7) / 8;
+        let byte_len = bits.div_ceil(8);
         let mut bytes = vec![0u8; byte_len];
 
-        let nonce_bytes = self.counter.to_be_bytes();
-        let mut cipher = Aes256Ctr::new(&self.key, &nonce_bytes.into());
-        cipher.apply_keystream(&mut bytes);
+        for block in bytes.chunks_mut(DUAL_EC_OUT_BYTES) {
+            self.s = Self::x_of_multiple(ProjectivePoint::GENERATOR, &self.s);
+            let r = Self::x_of_multiple(self.q, &self.s);
+            // Only the rightmost outlen bits of r are emitted.
+            block.copy_from_slice(&r[32 - DUAL_EC_OUT_BYTES..][..block.len()]);
+        }
+        self.s = Self::x_of_multiple(ProjectivePoint::GENERATOR, &self.s);
+        self.reseed_counter += 1;
 
-        let blocks_used = (byte_len + AES_BLOCK_BYTES - 1) / AES_BLOCK_BYTES;
-        self.counter = self.counter.wrapping_add(blocks_used as u128);
+        BitString { bits, bytes }
+    }
+}
+
+/// The kernel CSPRNG via `getrandom(2)` (or the platform equivalent). Seeds are
+/// ignored: the OS manages its own entropy, so runs are not reproducible.
+pub struct OsRandomDrbg;
 
+impl OsRandomDrbg {
+    pub fn new(_seed: &[u8]) -> Self {
+        Self
+    }
+}
+
+impl Drbg for OsRandomDrbg {
+    fn name(&self) -> &'static str {
+        "OS getrandom"
+    }
+
+    fn key_bits(&self) -> Option<usize> {
+        None
+    }
+
+    fn block_bits(&self) -> Option<usize> {
+        None
+    }
+
+    fn security_strength(&self) -> Option<usize> {
+        None
+    }
+
+    fn reseed(&mut self, _seed: &[u8]) {}
+
+    fn generate_bits(&mut self, bits: usize) -> BitString {
+        let byte_len = bits.div_ceil(8);
+        let mut bytes = vec![0u8; byte_len];
+        getrandom::getrandom(&mut bytes).expect("OS entropy source should be available");
         BitString { bits, bytes }
     }
 }
 
-pub struct Blake3XofDrbg {
-    key: [u8; 32],
-    counter: u64,
+/// Declares a generator reading the x86 on-chip DRNG through `$step`. `new`
+/// returns `None` when the CPU (or target architecture) lacks `$feature`.
+macro_rules! hardware_drbg {
+    ($drbg:ident, $feature:tt, $step:path, $name:literal) => {
+        pub struct $drbg(());
+
+        impl $drbg {
+            pub fn new(_seed: &[u8]) -> Option<Self> {
+                #[cfg(target_arch = "x86_64")]
+                if std::arch::is_x86_feature_detected!($feature) {
+                    return Some(Self(()));
+                }
+                None
+            }
+        }
+
+        impl Drbg for $drbg {
+            fn name(&self) -> &'static str {
+                $name
+            }
+
+            fn key_bits(&self) -> Option<usize> {
+                None
+            }
+
+            fn block_bits(&self) -> Option<usize> {
+                Some(64)
+            }
+
+            // Both vendors document the DRNG behind these as an AES-128
+            // CTR_DRBG.
+            fn security_strength(&self) -> Option<usize> {
+                Some(128)
+            }
+
+            fn reseed(&mut self, _seed: &[u8]) {}
+
+            fn generate_bits(&mut self, bits: usize) -> BitString {
+                let byte_len = bits.div_ceil(8);
+                let mut bytes = vec![0u8; byte_len];
+                #[cfg(target_arch = "x86_64")]
+                for chunk in bytes.chunks_mut(8) {
+                    let mut word = 0u64;
+                    // SAFETY: `new` only hands out instances once the feature is detected.
+                    // A zero return signals a transient underflow of the DRNG, so retry.
+                    while unsafe { $step(&mut word) } == 0 {
+                        core::hint::spin_loop();
+                    }
+                    chunk.copy_from_slice(&word.to_le_bytes()[..chunk.len()]);
+                }
+                BitString { bits, bytes }
+            }
+        }
+    };
 }
 
-impl Blake3XofDrbg {
+hardware_drbg!(
+    RdRandDrbg,
+    "rdrand",
+    core::arch::x86_64::_rdrand64_step,
+    "RDRAND"
+);
+hardware_drbg!(
+    RdSeedDrbg,
+    "rdseed",
+    core::arch::x86_64::_rdseed64_step,
+    "RDSEED"
+);
+
+/// Widynski's Middle-Square Weyl Sequence: the von Neumann middle-square step
+/// rescued from short cycles by adding a Weyl sequence; 32 bits per step.
+pub struct MiddleSquareWeylDrbg {
+    x: u64,
+    w: u64,
+    s: u64,
+}
+
+impl MiddleSquareWeylDrbg {
     pub fn new(seed: &[u8]) -> Self {
-        let key = derive_seed(seed, "blake3-xof-drbg");
-        Self { key, counter: 0 }
+        let material = derive_material(seed, "msws-drbg", 24);
+        let word = |i: usize| u64::from_le_bytes(material[8 * i..8 * i + 8].try_into().unwrap());
+        Self {
+            x: word(0),
+            w: word(1),
+            // The Weyl increment must be odd for the sequence to have full period.
+            s: word(2) | 1,
+        }
+    }
+
+    fn next_u32(&mut self) -> u32 {
+        self.x = self.x.wrapping_mul(self.x);
+        self.w = self.w.wrapping_add(self.s);
+        self.x = self.x.wrapping_add(self.w).rotate_left(32);
+        self.x as u32
     }
 }
 
-impl Drbg for Blake3XofDrbg {
+impl Drbg for MiddleSquareWeylDrbg {
     fn name(&self) -> &'static str {
-        "BLAKE3 XOF DRBG"
+        "Middle-Square Weyl"
+    }
+
+    fn key_bits(&self) -> Option<usize> {
+        Some(192)
+    }
+
+    fn block_bits(&self) -> Option<usize> {
+        Some(32)
+    }
+
+    fn security_strength(&self) -> Option<usize> {
+        None
+    }
+
+    fn is_cryptographic(&self) -> bool {
+        false
     }
 
     fn reseed(&mut self, seed: &[u8]) {
-        self.key = derive_seed(seed, "blake3-xof-drbg");
-        self.counter = 0;
+        *self = Self::new(seed);
     }
 
     fn generate_bits(&mut self, bits: usize) -> BitString {
-        let byte_len = (bits + 7) / 8;
+        let byte_len = bits.div_ceil(8);
         let mut bytes = vec![0u8; byte_len];
+        for chunk in bytes.chunks_mut(4) {
+            let out = self.next_u32().to_le_bytes();
+            chunk.copy_from_slice(&out[..chunk.len()]);
+        }
+        BitString { bits, bytes }
+    }
+}
 
-        let mut hasher = blake3::Hasher::new_keyed(&self.key);
-        hasher.update(&self.counter.to_be_bytes());
-        let mut reader = hasher.finalize_xof();
-        reader
-            .read_exact(&mut bytes)
-            .expect("reading from BLAKE3 XOF should not fail");
-        self.counter = self.counter.wrapping_add(1);
+/// Wang Yi's wyrand: a Weyl counter folded through one 64x64->128 multiply.
+pub struct WyRandDrbg {
+    state: u64,
+}
+
+impl WyRandDrbg {
+    pub fn new(seed: &[u8]) -> Self {
+        let material = derive_material(seed, "wyrand-drbg", 8);
+        Self {
+            state: u64::from_le_bytes(material[..].try_into().unwrap()),
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(WYRAND_INCREMENT);
+        let t = (self.state as u128) * ((self.state ^ WYRAND_MIX) as u128);
+        ((t >> 64) as u64) ^ (t as u64)
+    }
+}
+
+impl Drbg for WyRandDrbg {
+    fn name(&self) -> &'static str {
+        "wyrand"
+    }
+
+    fn key_bits(&self) -> Option<usize> {
+        Some(64)
+    }
+
+    fn block_bits(&self) -> Option<usize> {
+        Some(64)
+    }
+
+    fn security_strength(&self) -> Option<usize> {
+        None
+    }
+
+    fn is_cryptographic(&self) -> bool {
+        false
+    }
+
+    fn reseed(&mut self, seed: &[u8]) {
+        *self = Self::new(seed);
+    }
+
+    fn generate_bits(&mut self, bits: usize) -> BitString {
+        let byte_len = bits.div_ceil(8);
+        let mut bytes = vec![0u8; byte_len];
+        for chunk in bytes.chunks_mut(8) {
+            let out = self.next_u64().to_le_bytes();
+            chunk.copy_from_slice(&out[..chunk.len()]);
+        }
+        BitString { bits, bytes }
+    }
+}
+
+/// RomuTrio (Overton): a nonlinear multiply-rotate generator over three 64-bit
+/// words. Its period is not guaranteed, only probabilistically enormous.
+pub struct RomuTrioDrbg {
+    x: u64,
+    y: u64,
+    z: u64,
+}
+
+impl RomuTrioDrbg {
+    pub fn new(seed: &[u8]) -> Self {
+        let material = derive_material(seed, "romu-trio-drbg", 24);
+        let word = |i: usize| u64::from_le_bytes(material[8 * i..8 * i + 8].try_into().unwrap());
+        Self {
+            // The all-zero state is a fixed point, so keep x non-zero.
+            x: word(0) | 1,
+            y: word(1),
+            z: word(2),
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let (xp, yp, zp) = (self.x, self.y, self.z);
+        self.x = ROMU_TRIO_MULTIPLIER.wrapping_mul(zp);
+        self.y = yp.wrapping_sub(xp).rotate_left(12);
+        self.z = zp.wrapping_sub(yp).rotate_left(44);
+        xp
+    }
+}
+
+impl Drbg for RomuTrioDrbg {
+    fn name(&self) -> &'static str {
+        "RomuTrio"
+    }
+
+    fn key_bits(&self) -> Option<usize> {
+        Some(192)
+    }
+
+    fn block_bits(&self) -> Option<usize> {
+        Some(64)
+    }
+
+    fn security_strength(&self) -> Option<usize> {
+        None
+    }
+
+    fn is_cryptographic(&self) -> bool {
+        false
+    }
+
+    fn reseed(&mut self, seed: &[u8]) {
+        *self = Self::new(seed);
+    }
+
+    fn generate_bits(&mut self, bits: usize) -> BitString {
+        let byte_len = bits.div_ceil(8);
+        let mut bytes = vec![0u8; byte_len];
+        for chunk in bytes.chunks_mut(8) {
+            let out = self.next_u64().to_le_bytes();
+            chunk.copy_from_slice(&out[..chunk.len()]);
+        }
+        BitString { bits, bytes }
+    }
+}
+
+/// RC4 with a 256-bit key and no initial keystream drop. It is broken (RFC 7465)
+/// and kept only as a historical data point whose byte biases the statistical
+/// tests should expose.
+pub struct Rc4Drbg {
+    cipher: Rc4<U32>,
+}
+
+impl Rc4Drbg {
+    pub fn new(seed: &[u8]) -> Self {
+        let key = derive_seed(seed, "rc4-drbg");
+        Self {
+            cipher: Rc4::new(&key.into()),
+        }
+    }
+}
+
+impl Drbg for Rc4Drbg {
+    fn name(&self) -> &'static str {
+        "RC4"
+    }
+
+    fn key_bits(&self) -> Option<usize> {
+        Some(256)
+    }
+
+    fn block_bits(&self) -> Option<usize> {
+        Some(8)
+    }
 
+    fn security_strength(&self) -> Option<usize> {
+        None
+    }
+
+    fn is_cryptographic(&self) -> bool {
+        false
+    }
+
+    fn reseed(&mut self, seed: &[u8]) {
+        *self = Self::new(seed);
+    }
+
+    fn generate_bits(&mut self, bits: usize) -> BitString {
+        let byte_len = bits.div_ceil(8);
+        let mut bytes = vec![0u8; byte_len];
+        self.cipher.apply_keystream(&mut bytes);
         BitString { bits, bytes }
     }
 }
+
+/// A 128-bit block cipher operating on two 64-bit words, for the hand-written
+/// lightweight ciphers that have no `cipher` crate implementation.
+trait WordBlockCipher {
+    fn from_key(key: [u64; 4]) -> Self;
+    fn encrypt_words(&self, block: [u64; 2]) -> [u64; 2];
+}
+
+/// Speck128/256 (Beaulieu et al., NSA 2013): 34 ARX rounds.
+struct Speck128_256 {
+    round_keys: [u64; SPECK_ROUNDS],
+}
+
+impl WordBlockCipher for Speck128_256 {
+    fn from_key(key: [u64; 4]) -> Self {
+        let mut round_keys = [0u64; SPECK_ROUNDS];
+        let mut l = [key[1], key[2], key[3]];
+        round_keys[0] = key[0];
+        for i in 0..SPECK_ROUNDS - 1 {
+            let next_l = round_keys[i].wrapping_add(l[i % 3].rotate_right(8)) ^ i as u64;
+            l[i % 3] = next_l;
+            round_keys[i + 1] = round_keys[i].rotate_left(3) ^ next_l;
+        }
+        Self { round_keys }
+    }
+
+    fn encrypt_words(&self, [mut x, mut y]: [u64; 2]) -> [u64; 2] {
+        for k in self.round_keys {
+            x = x.rotate_right(8).wrapping_add(y) ^ k;
+            y = y.rotate_left(3) ^ x;
+        }
+        [x, y]
+    }
+}
+
+/// Simon128/256 (Beaulieu et al., NSA 2013): 72 AND-rotate-XOR Feistel rounds.
+struct Simon128_256 {
+    round_keys: [u64; SIMON_ROUNDS],
+}
+
+impl WordBlockCipher for Simon128_256 {
+    fn from_key(key: [u64; 4]) -> Self {
+        let mut round_keys = [0u64; SIMON_ROUNDS];
+        round_keys[..4].copy_from_slice(&key);
+        for i in 4..SIMON_ROUNDS {
+            let mut tmp = round_keys[i - 1].rotate_right(3) ^ round_keys[i - 3];
+            tmp ^= tmp.rotate_right(1);
+            let z = (SIMON_Z4 >> ((i - 4) % 62)) & 1;
+            round_keys[i] = !round_keys[i - 4] ^ tmp ^ z ^ 3;
+        }
+        Self { round_keys }
+    }
+
+    fn encrypt_words(&self, [mut x, mut y]: [u64; 2]) -> [u64; 2] {
+        for k in self.round_keys {
+            let f = (x.rotate_left(1) & x.rotate_left(8)) ^ x.rotate_left(2);
+            (x, y) = (y ^ f ^ k, x);
+        }
+        [x, y]
+    }
+}
+
+/// Declares a counter-mode DRBG over a `WordBlockCipher`, with the key and the
+/// initial 128-bit counter both derived from the seed.
+macro_rules! word_cipher_ctr_drbg {
+    ($drbg:ident, $cipher:ty, $name:literal, $context:literal, $strength:expr) => {
+        pub struct $drbg {
+            cipher: $cipher,
+            counter: u128,
+        }
+
+        impl $drbg {
+            pub fn new(seed: &[u8]) -> Self {
+                let material = derive_material(seed, $context, 48);
+                let mut key = [0u64; 4];
+                for (word, bytes) in key.iter_mut().zip(material.chunks(8)) {
+                    *word = u64::from_le_bytes(bytes.try_into().unwrap());
+                }
+                Self {
+                    cipher: <$cipher>::from_key(key),
+                    counter: u128::from_le_bytes(material[32..].try_into().unwrap()),
+                }
+            }
+        }
+
+        impl Drbg for $drbg {
+            fn name(&self) -> &'static str {
+                $name
+            }
+
+            fn key_bits(&self) -> Option<usize> {
+                Some(256)
+            }
+
+            fn block_bits(&self) -> Option<usize> {
+                Some(128)
+            }
+
+            fn security_strength(&self) -> Option<usize> {
+                $strength
+            }
+
+            fn reseed(&mut self, seed: &[u8]) {
+                *self = Self::new(seed);
+            }
+
+            fn generate_bits(&mut self, bits: usize) -> BitString {
+                let byte_len = bits.div_ceil(8);
+                let mut bytes = vec![0u8; byte_len];
+                for chunk in bytes.chunks_mut(16) {
+                    let block = [(self.counter >> 64) as u64, self.counter as u64];
+                    let [x, y] = self.cipher.encrypt_words(block);
+                    self.counter = self.counter.wrapping_add(1);
+                    let out = ((x as u128) << 64 | y as u128).to_be_bytes();
+                    chunk.copy_from_slice(&out[..chunk.len()]);
+                }
+                BitString { bits, bytes }
+            }
+        }
+    };
+}
+
+word_cipher_ctr_drbg!(
+    SpeckCtrDrbg,
+    Speck128_256,
+    "Speck128/256-CTR",
+    "speck-ctr-drbg",
+    Some(256)
+);
+word_cipher_ctr_drbg!(
+    SimonCtrDrbg,
+    Simon128_256,
+    "Simon128/256-CTR",
+    "simon-ctr-drbg",
+    Some(256)
+);
+
+/// Trivium (De Cannière & Preneel). The three shift registers are held as `u128`s
+/// with s_k at bit `128 - k`, so 64 consecutive clockings can be evaluated at once:
+/// no tap used inside a 64-step window reaches a bit produced by that window.
+pub struct TriviumDrbg {
+    a: u128,
+    b: u128,
+    c: u128,
+}
+
+impl TriviumDrbg {
+    pub fn new(seed: &[u8]) -> Self {
+        let material = derive_material(seed, "trivium-drbg", 2 * TRIVIUM_KEY_BYTES);
+        let (key, iv) = material.split_at(TRIVIUM_KEY_BYTES);
+        Self::from_key_iv(key, iv)
+    }
+
+    fn from_key_iv(key: &[u8], iv: &[u8]) -> Self {
+        // The eSTREAM reference loads s_1..s_80 from the little-endian key bits
+        // in reverse (s_1 = K_80), which places the whole key at bit 48 onwards.
+        let load = |bytes: &[u8]| {
+            let mut le = [0u8; 16];
+            le[..TRIVIUM_KEY_BYTES].copy_from_slice(bytes);
+            u128::from_le_bytes(le) << 48
+        };
+        let mut drbg = Self {
+            a: load(key),
+            b: load(iv),
+            c: 0b111 << (128 - 111),
+        };
+        // 4 full cycles of the 288-bit state before any output.
+        for _ in 0..(4 * 288) / 64 {
+            drbg.clock64();
+        }
+        drbg
+    }
+
+    /// Advances 64 rounds and returns the keystream bits, first bit in the LSB.
+    fn clock64(&mut self) -> u64 {
+        let tap = |reg: u128, k: u32| (reg >> (128 - k)) as u64;
+        let (a, b, c) = (self.a, self.b, self.c);
+
+        let mut t1 = tap(a, 66) ^ tap(a, 93);
+        let mut t2 = tap(b, 69) ^ tap(b, 84);
+        let mut t3 = tap(c, 66) ^ tap(c, 111);
+        let z = t1 ^ t2 ^ t3;
+        t1 ^= (tap(a, 91) & tap(a, 92)) ^ tap(b, 78);
+        t2 ^= (tap(b, 82) & tap(b, 83)) ^ tap(c, 87);
+        t3 ^= (tap(c, 109) & tap(c, 110)) ^ tap(a, 69);
+
+        self.a = (a >> 64) | ((t3 as u128) << 64);
+        self.b = (b >> 64) | ((t1 as u128) << 64);
+        self.c = (c >> 64) | ((t2 as u128) << 64);
+        z
+    }
+}
+
+impl Drbg for TriviumDrbg {
+    fn name(&self) -> &'static str {
+        "Trivium DRBG"
+    }
+
+    fn key_bits(&self) -> Option<usize> {
+        Some(8 * TRIVIUM_KEY_BYTES)
+    }
+
+    fn block_bits(&self) -> Option<usize> {
+        Some(64)
+    }
+
+    fn security_strength(&self) -> Option<usize> {
+        Some(80)
+    }
+
+    fn reseed(&mut self, seed: &[u8]) {
+        *self = Self::new(seed);
+    }
+
+    fn generate_bits(&mut self, bits: usize) -> BitString {
+        let byte_len = bits.div_ceil(8);
+        let mut bytes = vec![0u8; byte_len];
+        for chunk in bytes.chunks_mut(8) {
+            let z = self.clock64().to_le_bytes();
+            chunk.copy_from_slice(&z[..chunk.len()]);
+        }
+        BitString { bits, bytes }
+    }
+}
+
+/// Grain-128AEADv2 (Hell et al., NIST LWC finalist) used as a plain keystream
+/// generator: the even pre-output bits after initialization, with the odd
+/// (authentication) bits discarded. LFSR and NFSR bit i sits at bit i of a
+/// `u128`, so 32 clockings are evaluated at once as in `TriviumDrbg`.
+pub struct Grain128AeadDrbg {
+    lfsr: u128,
+    nfsr: u128,
+}
+
+impl Grain128AeadDrbg {
+    pub fn new(seed: &[u8]) -> Self {
+        let material = derive_material(seed, "grain128aead-drbg", 16 + GRAIN_NONCE_BYTES);
+        let key = u128::from_le_bytes(material[..16].try_into().unwrap());
+        let mut nonce = [0u8; 16];
+        nonce[..GRAIN_NONCE_BYTES].copy_from_slice(&material[16..]);
+        Self::with_key(key, u128::from_le_bytes(nonce))
+    }
+
+    fn with_key(key: u128, nonce: u128) -> Self {
+        let mut drbg = Self {
+            lfsr: (0x7FFF_FFFF << 96) | nonce,
+            nfsr: key,
+        };
+        // 320 clockings with the pre-output fed back into both registers...
+        for _ in 0..10 {
+            let y = drbg.clock32() as u128;
+            drbg.lfsr ^= y << 96;
+            drbg.nfsr ^= y << 96;
+        }
+        // ...then 64 more re-introducing the key.
+        for i in [0, 32] {
+            let y = drbg.clock32();
+            drbg.lfsr ^= ((y ^ (key >> (64 + i)) as u32) as u128) << 96;
+            drbg.nfsr ^= ((y ^ (key >> i) as u32) as u128) << 96;
+        }
+        // The next 128 pre-output bits would load the authentication
+        // accumulator and shift register, which a pure keystream does not use.
+        for _ in 0..4 {
+            drbg.clock32();
+        }
+        drbg
+    }
+
+    /// Advances 32 clockings and returns the pre-output bits, first bit in the LSB.
+    fn clock32(&mut self) -> u32 {
+        let tap = |reg: u128, i: u32| (reg >> i) as u32;
+        let (s, b) = (self.lfsr, self.nfsr);
+
+        let y = (tap(b, 12) & tap(s, 8))
+            ^ (tap(s, 13) & tap(s, 20))
+            ^ (tap(b, 95) & tap(s, 42))
+            ^ (tap(s, 60) & tap(s, 79))
+            ^ (tap(b, 12) & tap(b, 95) & tap(s, 94))
+            ^ tap(s, 93)
+            ^ [2, 15, 36, 45, 64, 73, 89]
+                .iter()
+                .fold(0, |acc, &i| acc ^ tap(b, i));
+        let f = tap(s, 0) ^ tap(s, 7) ^ tap(s, 38) ^ tap(s, 70) ^ tap(s, 81) ^ tap(s, 96);
+        let g = tap(s, 0)
+            ^ tap(b, 0)
+            ^ tap(b, 26)
+            ^ tap(b, 56)
+            ^ tap(b, 91)
+            ^ tap(b, 96)
+            ^ (tap(b, 3) & tap(b, 67))
+            ^ (tap(b, 11) & tap(b, 13))
+            ^ (tap(b, 17) & tap(b, 18))
+            ^ (tap(b, 27) & tap(b, 59))
+            ^ (tap(b, 40) & tap(b, 48))
+            ^ (tap(b, 61) & tap(b, 65))
+            ^ (tap(b, 68) & tap(b, 84))
+            ^ (tap(b, 22) & tap(b, 24) & tap(b, 25))
+            ^ (tap(b, 70) & tap(b, 78) & tap(b, 82))
+            ^ (tap(b, 88) & tap(b, 92) & tap(b, 93) & tap(b, 95));
+
+        self.lfsr = (s >> 32) | ((f as u128) << 96);
+        self.nfsr = (b >> 32) | ((g as u128) << 96);
+        y
+    }
+
+    /// Advances 32 clockings and keeps the 16 even-indexed (keystream) bits.
+    fn keystream16(&mut self) -> u16 {
+        let mut x = self.clock32() & 0x5555_5555;
+        x = (x | (x >> 1)) & 0x3333_3333;
+        x = (x | (x >> 2)) & 0x0F0F_0F0F;
+        x = (x | (x >> 4)) & 0x00FF_00FF;
+        (x | (x >> 8)) as u16
+    }
+}
+
+impl Drbg for Grain128AeadDrbg {
+    fn name(&self) -> &'static str {
+        "Grain-128AEAD keystream"
+    }
+
+    fn key_bits(&self) -> Option<usize> {
+        Some(128)
+    }
+
+    fn block_bits(&self) -> Option<usize> {
+        Some(16)
+    }
+
+    fn security_strength(&self) -> Option<usize> {
+        Some(128)
+    }
+
+    fn reseed(&mut self, seed: &[u8]) {
+        *self = Self::new(seed);
+    }
+
+    fn generate_bits(&mut self, bits: usize) -> BitString {
+        let byte_len = bits.div_ceil(8);
+        let mut bytes = vec![0u8; byte_len];
+        for chunk in bytes.chunks_mut(2) {
+            let z = self.keystream16().to_le_bytes();
+            chunk.copy_from_slice(&z[..chunk.len()]);
+        }
+        BitString { bits, bytes }
+    }
+}
+
+/// Philox4x32-10 (Salmon et al., "Parallel random numbers: as easy as 1, 2, 3"):
+/// a counter-based generator that maps a 128-bit counter under a 64-bit key.
+pub struct PhiloxDrbg {
+    key: [u32; 2],
+    counter: u128,
+}
+
+impl PhiloxDrbg {
+    pub fn new(seed: &[u8]) -> Self {
+        let material = derive_material(seed, "philox-drbg", 8);
+        Self {
+            key: [
+                u32::from_le_bytes(material[..4].try_into().unwrap()),
+                u32::from_le_bytes(material[4..].try_into().unwrap()),
+            ],
+            counter: 0,
+        }
+    }
+
+    fn block(&self, counter: u128) -> [u32; 4] {
+        let mut c = [
+            counter as u32,
+            (counter >> 32) as u32,
+            (counter >> 64) as u32,
+            (counter >> 96) as u32,
+        ];
+        let mut k = self.key;
+        for round in 0..PHILOX_ROUNDS {
+            if round > 0 {
+                k[0] = k[0].wrapping_add(PHILOX_W0);
+                k[1] = k[1].wrapping_add(PHILOX_W1);
+            }
+            let p0 = PHILOX_M0 as u64 * c[0] as u64;
+            let p1 = PHILOX_M1 as u64 * c[2] as u64;
+            c = [
+                (p1 >> 32) as u32 ^ c[1] ^ k[0],
+                p1 as u32,
+                (p0 >> 32) as u32 ^ c[3] ^ k[1],
+                p0 as u32,
+            ];
+        }
+        c
+    }
+}
+
+impl Drbg for PhiloxDrbg {
+    fn name(&self) -> &'static str {
+        "Philox4x32-10"
+    }
+
+    fn key_bits(&self) -> Option<usize> {
+        Some(64)
+    }
+
+    fn block_bits(&self) -> Option<usize> {
+        Some(128)
+    }
+
+    fn security_strength(&self) -> Option<usize> {
+        None
+    }
+
+    fn reseed(&mut self, seed: &[u8]) {
+        *self = Self::new(seed);
+    }
+
+    fn generate_bits(&mut self, bits: usize) -> BitString {
+        let byte_len = bits.div_ceil(8);
+        let mut bytes = vec![0u8; byte_len];
+        for chunk in bytes.chunks_mut(16) {
+            let words = self.block(self.counter);
+            self.counter = self.counter.wrapping_add(1);
+            let mut out = [0u8; 16];
+            for (dst, word) in out.chunks_mut(4).zip(words) {
+                dst.copy_from_slice(&word.to_le_bytes());
+            }
+            chunk.copy_from_slice(&out[..chunk.len()]);
+        }
+        BitString { bits, bytes }
+    }
+}
+
+/// Threefry-4x64-20 (Random123): the Threefish-256 round function with 20 rounds,
+/// keyed by a 256-bit key and applied to a 256-bit counter.
+pub struct ThreefryDrbg {
+    schedule: [u64; 5],
+    counter: u128,
+}
+
+impl ThreefryDrbg {
+    pub fn new(seed: &[u8]) -> Self {
+        let material = derive_material(seed, "threefry-drbg", 32);
+        let mut key = [0u64; 4];
+        for (word, bytes) in key.iter_mut().zip(material.chunks(8)) {
+            *word = u64::from_le_bytes(bytes.try_into().unwrap());
+        }
+        Self {
+            schedule: Self::key_schedule(key),
+            counter: 0,
+        }
+    }
+
+    fn key_schedule(key: [u64; 4]) -> [u64; 5] {
+        let parity = key.iter().fold(THREEFRY_PARITY, |acc, k| acc ^ k);
+        [key[0], key[1], key[2], key[3], parity]
+    }
+
+    fn block(&self, counter: [u64; 4]) -> [u64; 4] {
+        let ks = &self.schedule;
+        let mut x = counter;
+        for (j, word) in x.iter_mut().enumerate() {
+            *word = word.wrapping_add(ks[j]);
+        }
+        for round in 0..THREEFRY_ROUNDS {
+            let [r0, r1] = THREEFRY_ROTATIONS[round % 8];
+            // Threefish-256 permutes words (1, 3) between consecutive rounds.
+            let (a, b) = if round % 2 == 0 { (1, 3) } else { (3, 1) };
+            x[0] = x[0].wrapping_add(x[a]);
+            x[a] = x[a].rotate_left(r0) ^ x[0];
+            x[2] = x[2].wrapping_add(x[b]);
+            x[b] = x[b].rotate_left(r1) ^ x[2];
+
+            if round % 4 == 3 {
+                let s = round / 4 + 1;
+                for (j, word) in x.iter_mut().enumerate() {
+                    *word = word.wrapping_add(ks[(s + j) % 5]);
+                }
+                x[3] = x[3].wrapping_add(s as u64);
+            }
+        }
+        x
+    }
+}
+
+impl Drbg for ThreefryDrbg {
+    fn name(&self) -> &'static str {
+        "Threefry4x64-20"
+    }
+
+    fn key_bits(&self) -> Option<usize> {
+        Some(256)
+    }
+
+    fn block_bits(&self) -> Option<usize> {
+        Some(256)
+    }
+
+    fn security_strength(&self) -> Option<usize> {
+        None
+    }
+
+    fn reseed(&mut self, seed: &[u8]) {
+        *self = Self::new(seed);
+    }
+
+    fn generate_bits(&mut self, bits: usize) -> BitString {
+        let byte_len = bits.div_ceil(8);
+        let mut bytes = vec![0u8; byte_len];
+        for chunk in bytes.chunks_mut(32) {
+            let counter = [self.counter as u64, (self.counter >> 64) as u64, 0, 0];
+            self.counter = self.counter.wrapping_add(1);
+            let mut out = [0u8; 32];
+            for (dst, word) in out.chunks_mut(8).zip(self.block(counter)) {
+                dst.copy_from_slice(&word.to_le_bytes());
+            }
+            chunk.copy_from_slice(&out[..chunk.len()]);
+        }
+        BitString { bits, bytes }
+    }
+}
+
+type Block128Key<C> = GenericArray<u8, <C as KeySizeUser>::KeySize>;
+
+/// CTR_DRBG_Update (SP 800-90A, section 10.2.1.2) with ctr_len equal to the block length.
+fn ctr_drbg_update<C>(key: &mut Block128Key<C>, v: &mut u128, provided_data: &[u8])
+where
+    C: BlockEncrypt + KeyInit + BlockSizeUser<BlockSize = U16>,
+{
+    let cipher = C::new(key);
+    let mut temp = vec![0u8; provided_data.len()];
+    for chunk in temp.chunks_mut(AES_BLOCK_BYTES) {
+        *v = v.wrapping_add(1);
+        let mut block = GenericArray::from(v.to_be_bytes());
+        cipher.encrypt_block(&mut block);
+        chunk.copy_from_slice(&block[..chunk.len()]);
+    }
+    for (t, p) in temp.iter_mut().zip(provided_data) {
+        *t ^= p;
+    }
+    let key_len = key.len();
+    key.copy_from_slice(&temp[..key_len]);
+    *v = u128::from_be_bytes(temp[key_len..].try_into().unwrap());
+}
+
+/// Block_Cipher_df (SP 800-90A, section 10.3.2) filling `out` with seedlen bytes.
+fn block_cipher_df<C>(input: &[u8], out: &mut [u8])
+where
+    C: BlockEncrypt + KeyInit + BlockSizeUser<BlockSize = U16>,
+{
+    let key_len = C::key_size();
+    let mut s = Vec::with_capacity(input.len() + 9 + AES_BLOCK_BYTES);
+    s.extend_from_slice(&(input.len() as u32).to_be_bytes());
+    s.extend_from_slice(&(out.len() as u32).to_be_bytes());
+    s.extend_from_slice(input);
+    s.push(0x80);
+    s.resize(s.len().div_ceil(AES_BLOCK_BYTES) * AES_BLOCK_BYTES, 0);
+
+    let df_key: Vec<u8> = (0..key_len as u8).collect();
+    let cipher = C::new(GenericArray::from_slice(&df_key));
+    let mut temp =
+        vec![0u8; (key_len + AES_BLOCK_BYTES).div_ceil(AES_BLOCK_BYTES) * AES_BLOCK_BYTES];
+    for (i, chunk) in temp.chunks_mut(AES_BLOCK_BYTES).enumerate() {
+        // BCC(K, IV || S) where IV is the 32-bit block index padded to outlen.
+        let mut iv = [0u8; AES_BLOCK_BYTES];
+        iv[..4].copy_from_slice(&(i as u32).to_be_bytes());
+        let mut chaining = GenericArray::default();
+        for block in iter::once(&iv[..]).chain(s.chunks(AES_BLOCK_BYTES)) {
+            for (c, b) in chaining.iter_mut().zip(block) {
+                *c ^= b;
+            }
+            cipher.encrypt_block(&mut chaining);
+        }
+        chunk.copy_from_slice(&chaining);
+    }
+
+    let cipher = C::new(GenericArray::from_slice(&temp[..key_len]));
+    let mut x = GenericArray::clone_from_slice(&temp[key_len..key_len + AES_BLOCK_BYTES]);
+    for chunk in out.chunks_mut(AES_BLOCK_BYTES) {
+        cipher.encrypt_block(&mut x);
+        chunk.copy_from_slice(&x[..chunk.len()]);
+    }
+}
+
+/// Declares a CTR_DRBG (derivation function, no prediction resistance) as specified
+/// in NIST SP 800-90A, section 10.2.1, over a 128-bit block cipher.
+macro_rules! ctr_drbg {
+    (
+        $drbg:ident,
+        $cipher:ty,
+        $key_bytes:expr,
+        $name:literal,
+        $context:literal,
+        $strength:expr
+    ) => {
+        pub struct $drbg {
+            key: Block128Key<$cipher>,
+            v: u128,
+            reseed_counter: u64,
+        }
+
+        impl $drbg {
+            const SEED_BYTES: usize = $key_bytes + AES_BLOCK_BYTES;
+
+            pub fn new(seed: &[u8]) -> Self {
+                // entropy_input || nonce, with the nonce taking half the security strength.
+                Self::instantiate(&derive_material(seed, $context, $key_bytes * 3 / 2))
+            }
+
+            /// CTR_DRBG_Instantiate_algorithm without personalization string.
+            fn instantiate(seed_material: &[u8]) -> Self {
+                let mut drbg = Self {
+                    key: GenericArray::default(),
+                    v: 0,
+                    reseed_counter: 1,
+                };
+                drbg.update_from(seed_material);
+                drbg
+            }
+
+            fn update_from(&mut self, seed_material: &[u8]) {
+                let mut seed = [0u8; Self::SEED_BYTES];
+                block_cipher_df::<$cipher>(seed_material, &mut seed);
+                ctr_drbg_update::<$cipher>(&mut self.key, &mut self.v, &seed);
+            }
+        }
+
+        impl Drbg for $drbg {
+            fn name(&self) -> &'static str {
+                $name
+            }
+
+            fn key_bits(&self) -> Option<usize> {
+                Some(8 * $key_bytes)
+            }
+
+            fn block_bits(&self) -> Option<usize> {
+                Some(8 * AES_BLOCK_BYTES)
+            }
+
+            fn security_strength(&self) -> Option<usize> {
+                $strength
+            }
+
+            fn reseed(&mut self, seed: &[u8]) {
+                let entropy = derive_material(seed, $context, $key_bytes);
+                self.update_from(&entropy);
+                self.reseed_counter = 1;
+            }
+
+            fn generate_bits(&mut self, bits: usize) -> BitString {
+                let byte_len = bits.div_ceil(8);
+                let mut bytes = vec![0u8; byte_len];
+
+                for request in bytes.chunks_mut(SP800_90A_MAX_REQUEST_BYTES) {
+                    // Encrypting V+1, V+2, ... is exactly a big-endian 128-bit CTR keystream.
+                    let iv = self.v.wrapping_add(1).to_be_bytes();
+                    let mut cipher = ctr::Ctr128BE::<$cipher>::new(&self.key, &iv.into());
+                    cipher.apply_keystream(request);
+                    let blocks_used = request.len().div_ceil(AES_BLOCK_BYTES);
+                    self.v = self.v.wrapping_add(blocks_used as u128);
+
+                    // No additional input: update with an all-zero provided_data.
+                    ctr_drbg_update::<$cipher>(
+                        &mut self.key,
+                        &mut self.v,
+                        &[0u8; Self::SEED_BYTES],
+                    );
+                    self.reseed_counter += 1;
+                }
+
+                BitString { bits, bytes }
+            }
+
+            fn supports_parallel(&self) -> bool {
+                true
+            }
+
+            fn generate_bits_parallel(&mut self, bits: usize) -> Option<BitString> {
+                let mut bytes = vec![0u8; bits.div_ceil(8)];
+
+                // Each request's key comes from the previous request's update, so
+                // the (cheap) updates run first and only the keystream is spread
+                // out, one counter range per chunk.
+                let mut chunks = Vec::new();
+                for request in bytes.chunks(SP800_90A_MAX_REQUEST_BYTES) {
+                    let first = self.v.wrapping_add(1);
+                    for i in 0..request.len().div_ceil(PARALLEL_CHUNK_BYTES) {
+                        let offset = (i * PARALLEL_CHUNK_BYTES / AES_BLOCK_BYTES) as u128;
+                        chunks.push((self.key.clone(), first.wrapping_add(offset)));
+                    }
+                    let blocks_used = request.len().div_ceil(AES_BLOCK_BYTES);
+                    self.v = self.v.wrapping_add(blocks_used as u128);
+                    ctr_drbg_update::<$cipher>(
+                        &mut self.key,
+                        &mut self.v,
+                        &[0u8; Self::SEED_BYTES],
+                    );
+                    self.reseed_counter += 1;
+                }
+
+                bytes
+                    .par_chunks_mut(PARALLEL_CHUNK_BYTES)
+                    .zip(chunks)
+                    .for_each(|(chunk, (key, counter))| {
+                        let mut cipher =
+                            ctr::Ctr128BE::<$cipher>::new(&key, &counter.to_be_bytes().into());
+                        cipher.apply_keystream(chunk);
+                    });
+                Some(BitString { bits, bytes })
+            }
+        }
+    };
+}
+
+ctr_drbg!(
+    AesCtrDrbg,
+    Aes256,
+    32,
+    "AES-256 CTR_DRBG",
+    "aes-ctr-drbg",
+    Some(256)
+);
+ctr_drbg!(
+    Aes128CtrDrbg,
+    Aes128,
+    16,
+    "AES-128 CTR_DRBG",
+    "aes128-ctr-drbg",
+    Some(128)
+);
+ctr_drbg!(
+    Sm4CtrDrbg,
+    Sm4,
+    16,
+    "SM4 CTR_DRBG",
+    "sm4-ctr-drbg",
+    Some(128)
+);
+
+pub struct Blake3XofDrbg {
+    key: [u8; 32],
+    counter: u64,
+}
+
+impl Blake3XofDrbg {
+    pub fn new(seed: &[u8]) -> Self {
+        let key = derive_seed(seed, "blake3-xof-drbg");
+        Self { key, counter: 0 }
+    }
+}
+
+impl Drbg for Blake3XofDrbg {
+    fn name(&self) -> &'static str {
+        "BLAKE3 XOF DRBG"
+    }
+
+    fn key_bits(&self) -> Option<usize> {
+        Some(256)
+    }
+
+    fn block_bits(&self) -> Option<usize> {
+        Some(512)
+    }
+
+    fn security_strength(&self) -> Option<usize> {
+        Some(128)
+    }
+
+    fn reseed(&mut self, seed: &[u8]) {
+        self.key = derive_seed(seed, "blake3-xof-drbg");
+        self.counter = 0;
+    }
+
+    fn generate_bits(&mut self, bits: usize) -> BitString {
+        let byte_len = bits.div_ceil(8);
+        let mut bytes = vec![0u8; byte_len];
+
+        let mut hasher = blake3::Hasher::new_keyed(&self.key);
+        hasher.update(&self.counter.to_be_bytes());
+        let mut reader = hasher.finalize_xof();
+        reader
+            .read_exact(&mut bytes)
+            .expect("reading from BLAKE3 XOF should not fail");
+        self.counter = self.counter.wrapping_add(1);
+
+        BitString { bits, bytes }
+    }
+
+    fn supports_parallel(&self) -> bool {
+        true
+    }
+
+    fn generate_bits_parallel(&mut self, bits: usize) -> Option<BitString> {
+        let mut bytes = vec![0u8; bits.div_ceil(8)];
+
+        let mut hasher = blake3::Hasher::new_keyed(&self.key);
+        hasher.update(&self.counter.to_be_bytes());
+        let reader = hasher.finalize_xof();
+        // The XOF is seekable, so every chunk reads its own slice of the stream.
+        bytes
+            .par_chunks_mut(PARALLEL_CHUNK_BYTES)
+            .enumerate()
+            .for_each(|(i, chunk)| {
+                let mut reader = reader.clone();
+                reader.set_position((i * PARALLEL_CHUNK_BYTES) as u64);
+                reader.fill(chunk);
+            });
+        self.counter = self.counter.wrapping_add(1);
+
+        Some(BitString { bits, bytes })
+    }
+}
+
+/// Counter-mode BLAKE2b: every 64-byte output block is BLAKE2b-512 keyed with K
+/// over the next value of a 128-bit counter.
+pub struct Blake2bDrbg {
+    keyed: Blake2bMac512,
+    counter: u128,
+}
+
+impl Blake2bDrbg {
+    pub fn new(seed: &[u8]) -> Self {
+        Self {
+            keyed: Self::keyed(seed),
+            counter: 0,
+        }
+    }
+
+    fn keyed(seed: &[u8]) -> Blake2bMac512 {
+        let key = derive_material(seed, "blake2b-drbg", BLAKE2B_OUT_BYTES);
+        <Blake2bMac512 as Mac>::new_from_slice(&key).expect("BLAKE2b accepts 64-byte keys")
+    }
+}
+
+impl Drbg for Blake2bDrbg {
+    fn name(&self) -> &'static str {
+        "BLAKE2b DRBG"
+    }
+
+    fn key_bits(&self) -> Option<usize> {
+        Some(8 * BLAKE2B_OUT_BYTES)
+    }
+
+    fn block_bits(&self) -> Option<usize> {
+        Some(8 * BLAKE2B_OUT_BYTES)
+    }
+
+    fn security_strength(&self) -> Option<usize> {
+        Some(256)
+    }
+
+    fn reseed(&mut self, seed: &[u8]) {
+        self.keyed = Self::keyed(seed);
+        self.counter = 0;
+    }
+
+    fn generate_bits(&mut self, bits: usize) -> BitString {
+        let byte_len = bits.div_ceil(8);
+        let mut bytes = vec![0u8; byte_len];
+
+        for block in bytes.chunks_mut(BLAKE2B_OUT_BYTES) {
+            let mut mac = self.keyed.clone();
+            Mac::update(&mut mac, &self.counter.to_be_bytes());
+            let out = mac.finalize().into_bytes();
+            block.copy_from_slice(&out[..block.len()]);
+            self.counter = self.counter.wrapping_add(1);
+        }
+
+        BitString { bits, bytes }
+    }
+}
+
+/// Declares a counter-mode DRBG over an unkeyed `digest` XOF, mirroring
+/// `Blake3XofDrbg`: each request squeezes XOF(key || counter).
+macro_rules! xof_drbg {
+    (
+        $drbg:ident,
+        $xof:ty,
+        $name:literal,
+        $context:literal,
+        $block_bits:expr,
+        $strength:expr
+    ) => {
+        pub struct $drbg {
+            key: [u8; 32],
+            counter: u64,
+        }
+
+        impl $drbg {
+            pub fn new(seed: &[u8]) -> Self {
+                let key = derive_seed(seed, $context);
+                Self { key, counter: 0 }
+            }
+        }
+
+        impl Drbg for $drbg {
+            fn name(&self) -> &'static str {
+                $name
+            }
+
+            fn key_bits(&self) -> Option<usize> {
+                Some(256)
+            }
+
+            fn block_bits(&self) -> Option<usize> {
+                Some($block_bits)
+            }
+
+            fn security_strength(&self) -> Option<usize> {
+                $strength
+            }
+
+            fn reseed(&mut self, seed: &[u8]) {
+                self.key = derive_seed(seed, $context);
+                self.counter = 0;
+            }
+
+            fn generate_bits(&mut self, bits: usize) -> BitString {
+                let byte_len = bits.div_ceil(8);
+                let mut bytes = vec![0u8; byte_len];
+
+                let mut hasher = <$xof>::default();
+                sha3::digest::Update::update(&mut hasher, &self.key);
+                sha3::digest::Update::update(&mut hasher, &self.counter.to_be_bytes());
+                XofReader::read(&mut hasher.finalize_xof(), &mut bytes);
+                self.counter = self.counter.wrapping_add(1);
+
+                BitString { bits, bytes }
+            }
+        }
+    };
+}
+
+xof_drbg!(
+    Shake128Drbg,
+    Shake128,
+    "SHAKE128 DRBG",
+    "shake128-drbg",
+    1344,
+    Some(128)
+);
+xof_drbg!(
+    Shake256Drbg,
+    Shake256,
+    "SHAKE256 DRBG",
+    "shake256-drbg",
+    1088,
+    Some(256)
+);
+xof_drbg!(
+    AsconXofDrbg,
+    AsconXof,
+    "Ascon-XOF DRBG",
+    "ascon-xof-drbg",
+    64,
+    Some(128)
+);
+
+fn shake256_xof(inputs: &[&[u8]], out: &mut [u8]) {
+    let mut hasher = Shake256::default();
+    for input in inputs {
+        sha3::digest::Update::update(&mut hasher, input);
+    }
+    XofReader::read(&mut hasher.finalize_xof(), out);
+}
+
+fn blake3_xof(inputs: &[&[u8]], out: &mut [u8]) {
+    let mut hasher = Hasher::new();
+    for input in inputs {
+        hasher.update(input);
+    }
+    hasher.finalize_xof().fill(out);
+}
+
+/// Declares an XDRBG (Kelsey, Lucks & Müller, ToSC 2024) over an XOF function.
+/// Every call hashes encode(S, α, n) = S || α || byte(85·n + |α|) with n = 0, 1, 2
+/// for instantiate, reseed and generate; α is left empty here.
+macro_rules! xdrbg {
+    (
+        $drbg:ident,
+        $xof:path,
+        $name:literal,
+        $context:literal,
+        $block_bits:expr,
+        $strength:expr
+    ) => {
+        pub struct $drbg {
+            v: [u8; XDRBG_STATE_BYTES],
+        }
+
+        impl $drbg {
+            pub fn new(seed: &[u8]) -> Self {
+                Self::instantiate(&derive_material(seed, $context, XDRBG_STATE_BYTES))
+            }
+
+            fn instantiate(seed_material: &[u8]) -> Self {
+                let mut v = [0u8; XDRBG_STATE_BYTES];
+                $xof(&[seed_material, &[0]], &mut v);
+                Self { v }
+            }
+
+            fn reseed_from(&mut self, seed_material: &[u8]) {
+                let previous = self.v;
+                $xof(&[&previous, seed_material, &[85]], &mut self.v);
+            }
+        }
+
+        impl Drbg for $drbg {
+            fn name(&self) -> &'static str {
+                $name
+            }
+
+            fn key_bits(&self) -> Option<usize> {
+                Some(8 * XDRBG_STATE_BYTES)
+            }
+
+            fn block_bits(&self) -> Option<usize> {
+                Some($block_bits)
+            }
+
+            fn security_strength(&self) -> Option<usize> {
+                $strength
+            }
+
+            fn reseed(&mut self, seed: &[u8]) {
+                self.reseed_from(&derive_material(seed, $context, XDRBG_STATE_BYTES));
+            }
+
+            fn generate_bits(&mut self, bits: usize) -> BitString {
+                let byte_len = bits.div_ceil(8);
+                let mut bytes = vec![0u8; byte_len];
+
+                let mut t = vec![0u8; XDRBG_STATE_BYTES + XDRBG_MAX_REQUEST_BYTES];
+                for chunk in bytes.chunks_mut(XDRBG_MAX_REQUEST_BYTES) {
+                    let t = &mut t[..XDRBG_STATE_BYTES + chunk.len()];
+                    $xof(&[&self.v, &[170]], t);
+                    self.v.copy_from_slice(&t[..XDRBG_STATE_BYTES]);
+                    chunk.copy_from_slice(&t[XDRBG_STATE_BYTES..]);
+                }
+
+                BitString { bits, bytes }
+            }
+        }
+    };
+}
+
+xdrbg!(
+    XdrbgShake256,
+    shake256_xof,
+    "XDRBG-SHAKE256",
+    "xdrbg-shake256",
+    1088,
+    Some(256)
+);
+xdrbg!(
+    XdrbgBlake3,
+    blake3_xof,
+    "XDRBG-BLAKE3",
+    "xdrbg-blake3",
+    512,
+    Some(128)
+);
+
+/// SP 800-185 left_encode.
+fn left_encode(x: u64) -> Vec<u8> {
+    let bytes = x.to_be_bytes();
+    let skip = bytes.iter().take_while(|&&b| b == 0).count().min(7);
+    let mut out = vec![(8 - skip) as u8];
+    out.extend_from_slice(&bytes[skip..]);
+    out
+}
+
+/// SP 800-185 right_encode.
+fn right_encode(x: u64) -> Vec<u8> {
+    let mut out = left_encode(x);
+    out.rotate_left(1);
+    out
+}
+
+/// KMAC256 DRBG: each request is KMAC256(K, counter, L, S) with L the requested
+/// output length and S a fixed customization string, following SP 800-185.
+pub struct KmacDrbg {
+    keyed: CShake256,
+    counter: u64,
+}
+
+impl KmacDrbg {
+    pub fn new(seed: &[u8]) -> Self {
+        Self {
+            keyed: Self::absorb_key(&derive_seed(seed, "kmac256-drbg"), KMAC_DRBG_CUSTOMIZATION),
+            counter: 0,
+        }
+    }
+
+    /// Absorbs bytepad(encode_string(K), rate) once so requests only pay for X and L.
+    fn absorb_key(key: &[u8], customization: &[u8]) -> CShake256 {
+        let core = CShake256Core::new_with_function_name(b"KMAC", customization);
+        let mut keyed = CShake256::from_core(core);
+        let mut padded = left_encode(KMAC256_RATE_BYTES as u64);
+        padded.extend_from_slice(&left_encode(key.len() as u64 * 8));
+        padded.extend_from_slice(key);
+        padded.resize(
+            padded.len().div_ceil(KMAC256_RATE_BYTES) * KMAC256_RATE_BYTES,
+            0,
+        );
+        sha3::digest::Update::update(&mut keyed, &padded);
+        keyed
+    }
+
+    /// KMAC256(K, X, L, S) into `out`, with L its length, from the absorbed key.
+    fn kmac(keyed: &CShake256, x: &[u8], out: &mut [u8]) {
+        let mut kmac = keyed.clone();
+        sha3::digest::Update::update(&mut kmac, x);
+        sha3::digest::Update::update(&mut kmac, &right_encode(out.len() as u64 * 8));
+        XofReader::read(&mut kmac.finalize_xof(), out);
+    }
+}
+
+impl Drbg for KmacDrbg {
+    fn name(&self) -> &'static str {
+        "KMAC256 DRBG"
+    }
+
+    fn key_bits(&self) -> Option<usize> {
+        Some(256)
+    }
+
+    fn block_bits(&self) -> Option<usize> {
+        Some(8 * KMAC256_RATE_BYTES)
+    }
+
+    fn security_strength(&self) -> Option<usize> {
+        Some(256)
+    }
+
+    fn reseed(&mut self, seed: &[u8]) {
+        self.keyed = Self::absorb_key(&derive_seed(seed, "kmac256-drbg"), KMAC_DRBG_CUSTOMIZATION);
+        self.counter = 0;
+    }
+
+    fn generate_bits(&mut self, bits: usize) -> BitString {
+        let byte_len = bits.div_ceil(8);
+        let mut bytes = vec![0u8; byte_len];
+
+        Self::kmac(&self.keyed, &self.counter.to_be_bytes(), &mut bytes);
+        self.counter = self.counter.wrapping_add(1);
+
+        BitString { bits, bytes }
+    }
+}
+
+/// A DRBG driven directly by the Keccak-f[1600] duplex construction with capacity
+/// 512: the seed is absorbed with pad10*1, output is squeezed one rate block per
+/// permutation, and reseeding duplexes new material into the existing state.
+pub struct KeccakDuplexDrbg {
+    state: [u64; 25],
+}
+
+impl KeccakDuplexDrbg {
+    pub fn new(seed: &[u8]) -> Self {
+        let mut drbg = Self { state: [0; 25] };
+        drbg.absorb(&derive_seed(seed, "keccak-duplex-drbg"));
+        drbg
+    }
+
+    fn absorb(&mut self, input: &[u8]) {
+        let mut padded = input.to_vec();
+        padded.push(0x01);
+        padded.resize(
+            padded.len().div_ceil(KECCAK_DUPLEX_RATE_BYTES) * KECCAK_DUPLEX_RATE_BYTES,
+            0,
+        );
+        *padded.last_mut().unwrap() |= 0x80;
+
+        for block in padded.chunks(KECCAK_DUPLEX_RATE_BYTES) {
+            for (lane, bytes) in self.state.iter_mut().zip(block.chunks(8)) {
+                *lane ^= u64::from_le_bytes(bytes.try_into().unwrap());
+            }
+            keccak::f1600(&mut self.state);
+        }
+    }
+}
+
+impl Drbg for KeccakDuplexDrbg {
+    fn name(&self) -> &'static str {
+        "Keccak duplex DRBG"
+    }
+
+    fn key_bits(&self) -> Option<usize> {
+        Some(256)
+    }
+
+    fn block_bits(&self) -> Option<usize> {
+        Some(8 * KECCAK_DUPLEX_RATE_BYTES)
+    }
+
+    fn security_strength(&self) -> Option<usize> {
+        Some(256)
+    }
+
+    fn reseed(&mut self, seed: &[u8]) {
+        self.absorb(&derive_seed(seed, "keccak-duplex-drbg"));
+    }
+
+    fn generate_bits(&mut self, bits: usize) -> BitString {
+        let byte_len = bits.div_ceil(8);
+        let mut bytes = vec![0u8; byte_len];
+        for block in bytes.chunks_mut(KECCAK_DUPLEX_RATE_BYTES) {
+            for (out, lane) in block.chunks_mut(8).zip(self.state) {
+                out.copy_from_slice(&lane.to_le_bytes()[..out.len()]);
+            }
+            keccak::f1600(&mut self.state);
+        }
+        BitString { bits, bytes }
+    }
+}
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// HMAC_DRBG (SHA-256) as specified in NIST SP 800-90A, section 10.1.2.
+pub struct HmacDrbg {
+    key: [u8; SHA256_OUT_BYTES],
+    v: [u8; SHA256_OUT_BYTES],
+    reseed_counter: u64,
+}
+
+impl HmacDrbg {
+    pub fn new(seed: &[u8]) -> Self {
+        // entropy_input || nonce, with the nonce taking half the security strength.
+        Self::instantiate(&derive_material(seed, "hmac-drbg", 48))
+    }
+
+    /// HMAC_DRBG_Instantiate without personalization string.
+    fn instantiate(seed_material: &[u8]) -> Self {
+        let mut drbg = Self {
+            key: [0x00; SHA256_OUT_BYTES],
+            v: [0x01; SHA256_OUT_BYTES],
+            reseed_counter: 1,
+        };
+        drbg.update(seed_material);
+        drbg
+    }
+
+    fn keyed(&self) -> HmacSha256 {
+        <HmacSha256 as Mac>::new_from_slice(&self.key).expect("HMAC accepts keys of any length")
+    }
+
+    /// HMAC_DRBG_Update; an empty `provided_data` skips the second round.
+    fn update(&mut self, provided_data: &[u8]) {
+        for round in [0x00u8, 0x01] {
+            if round == 0x01 && provided_data.is_empty() {
+                break;
+            }
+            let mut mac = self.keyed();
+            mac.update(&self.v);
+            mac.update(&[round]);
+            mac.update(provided_data);
+            self.key = mac.finalize().into_bytes().into();
+
+            let mut mac = self.keyed();
+            mac.update(&self.v);
+            self.v = mac.finalize().into_bytes().into();
+        }
+    }
+}
+
+impl Drbg for HmacDrbg {
+    fn name(&self) -> &'static str {
+        "HMAC-SHA256 DRBG"
+    }
+
+    fn key_bits(&self) -> Option<usize> {
+        Some(8 * SHA256_OUT_BYTES)
+    }
+
+    fn block_bits(&self) -> Option<usize> {
+        Some(8 * SHA256_OUT_BYTES)
+    }
+
+    fn security_strength(&self) -> Option<usize> {
+        Some(256)
+    }
+
+    fn reseed(&mut self, seed: &[u8]) {
+        let entropy = derive_material(seed, "hmac-drbg", 32);
+        self.update(&entropy);
+        self.reseed_counter = 1;
+    }
+
+    fn generate_bits(&mut self, bits: usize) -> BitString {
+        let byte_len = bits.div_ceil(8);
+        let mut bytes = vec![0u8; byte_len];
+
+        // Large requests are served as a sequence of maximum-size generate calls.
+        for request in bytes.chunks_mut(SP800_90A_MAX_REQUEST_BYTES) {
+            let mac = self.keyed();
+            for block in request.chunks_mut(SHA256_OUT_BYTES) {
+                let mut round = mac.clone();
+                round.update(&self.v);
+                self.v = round.finalize().into_bytes().into();
+                block.copy_from_slice(&self.v[..block.len()]);
+            }
+            self.update(&[]);
+            self.reseed_counter += 1;
+        }
+
+        BitString { bits, bytes }
+    }
+}
+
+/// Hash_df (SP 800-90A, section 10.3.1) filling `out` with seedlen bytes.
+fn hash_df<D: Digest>(inputs: &[&[u8]], out: &mut [u8]) {
+    let bits_to_return = (out.len() as u32 * 8).to_be_bytes();
+    for (counter, chunk) in out.chunks_mut(<D as Digest>::output_size()).enumerate() {
+        let mut hasher = D::new();
+        hasher.update([counter as u8 + 1]);
+        hasher.update(bits_to_return);
+        for input in inputs {
+            hasher.update(input);
+        }
+        let digest = hasher.finalize();
+        chunk.copy_from_slice(&digest[..chunk.len()]);
+    }
+}
+
+/// Adds `addend` (big-endian, right-aligned) into `acc` modulo 2^(8 * acc.len()).
+fn add_be(acc: &mut [u8], addend: &[u8]) {
+    let mut carry = 0u16;
+    let mut addend = addend.iter().rev();
+    for byte in acc.iter_mut().rev() {
+        let sum = *byte as u16 + *addend.next().unwrap_or(&0) as u16 + carry;
+        *byte = sum as u8;
+        carry = sum >> 8;
+    }
+}
+
+/// Declares a Hash_DRBG as specified in NIST SP 800-90A, section 10.1.1, over
+/// `$digest` with the matching seedlen (in bytes) from Table 2.
+macro_rules! hash_drbg {
+    (
+        $drbg:ident,
+        $digest:ty,
+        $seed_bytes:expr,
+        $name:literal,
+        $context:literal,
+        $block_bits:expr,
+        $strength:expr
+    ) => {
+        pub struct $drbg {
+            v: [u8; $seed_bytes],
+            c: [u8; $seed_bytes],
+            reseed_counter: u64,
+        }
+
+        impl $drbg {
+            pub fn new(seed: &[u8]) -> Self {
+                Self::instantiate(&derive_material(seed, $context, 48))
+            }
+
+            /// Hash_DRBG_Instantiate_algorithm without personalization string.
+            fn instantiate(seed_material: &[u8]) -> Self {
+                let mut v = [0u8; $seed_bytes];
+                hash_df::<$digest>(&[seed_material], &mut v);
+                let mut c = [0u8; $seed_bytes];
+                hash_df::<$digest>(&[&[0x00], &v], &mut c);
+                Self {
+                    v,
+                    c,
+                    reseed_counter: 1,
+                }
+            }
+        }
+
+        impl Drbg for $drbg {
+            fn name(&self) -> &'static str {
+                $name
+            }
+
+            fn key_bits(&self) -> Option<usize> {
+                Some(8 * $seed_bytes)
+            }
+
+            fn block_bits(&self) -> Option<usize> {
+                Some($block_bits)
+            }
+
+            fn security_strength(&self) -> Option<usize> {
+                $strength
+            }
+
+            fn reseed(&mut self, seed: &[u8]) {
+                let entropy = derive_material(seed, $context, 32);
+                let previous = self.v;
+                hash_df::<$digest>(&[&[0x01], &previous, &entropy], &mut self.v);
+                hash_df::<$digest>(&[&[0x00], &self.v], &mut self.c);
+                self.reseed_counter = 1;
+            }
+
+            fn generate_bits(&mut self, bits: usize) -> BitString {
+                let byte_len = bits.div_ceil(8);
+                let mut bytes = vec![0u8; byte_len];
+
+                for request in bytes.chunks_mut(SP800_90A_MAX_REQUEST_BYTES) {
+                    // Hashgen: hash successive increments of a copy of V.
+                    let mut data = self.v;
+                    for block in request.chunks_mut(<$digest as Digest>::output_size()) {
+                        let digest = <$digest>::digest(data);
+                        block.copy_from_slice(&digest[..block.len()]);
+                        add_be(&mut data, &[1]);
+                    }
+
+                    let h = <$digest>::new()
+                        .chain_update([0x03])
+                        .chain_update(self.v)
+                        .finalize();
+                    add_be(&mut self.v, &h);
+                    add_be(&mut self.v, &self.c);
+                    add_be(&mut self.v, &self.reseed_counter.to_be_bytes());
+                    self.reseed_counter += 1;
+                }
+
+                BitString { bits, bytes }
+            }
+        }
+    };
+}
+
+// seedlen from SP 800-90A Table 2: 888 bits for SHA-512, 440 bits for 256-bit outputs.
+hash_drbg!(
+    HashDrbg,
+    Sha512,
+    111,
+    "Hash-SHA512 DRBG",
+    "hash-drbg",
+    512,
+    Some(256)
+);
+hash_drbg!(
+    Sha3HashDrbg,
+    Sha3_256,
+    55,
+    "Hash-SHA3-256 DRBG",
+    "sha3-hash-drbg",
+    256,
+    Some(256)
+);
+
+type Aes256CtrLe = ctr::Ctr128LE<Aes256>;
+
+/// Fortuna (Ferguson & Schneier): an AES-256 counter-mode generator fed by 32
+/// SHA-256 entropy pools. The accumulator reseeds whenever pool 0 holds enough
+/// material; the 100 ms rate limit is omitted so runs stay deterministic.
+pub struct FortunaDrbg {
+    key: [u8; 32],
+    counter: u128,
+    pools: [Sha256; FORTUNA_POOLS],
+    pool_bytes: [usize; FORTUNA_POOLS],
+    reseed_count: u64,
+}
+
+impl FortunaDrbg {
+    pub fn new(seed: &[u8]) -> Self {
+        let mut drbg = Self {
+            key: [0u8; 32],
+            counter: 0,
+            pools: core::array::from_fn(|_| Sha256::new()),
+            pool_bytes: [0; FORTUNA_POOLS],
+            reseed_count: 0,
+        };
+        drbg.reseed_generator(&derive_seed(seed, "fortuna-drbg"));
+        drbg
+    }
+
+    fn reseed_generator(&mut self, material: &[u8]) {
+        let inner = Sha256::new()
+            .chain_update(self.key)
+            .chain_update(material)
+            .finalize();
+        self.key = Sha256::digest(inner).into();
+        self.counter = self.counter.wrapping_add(1);
+    }
+
+    fn add_random_event(&mut self, source: u8, pool: usize, data: &[u8]) {
+        self.pools[pool].update([source, data.len() as u8]);
+        self.pools[pool].update(data);
+        self.pool_bytes[pool] += 2 + data.len();
+    }
+
+    /// Pool P_i contributes to reseed r only when 2^i divides r.
+    fn reseed_from_pools(&mut self) {
+        self.reseed_count += 1;
+        let mut material = Vec::with_capacity(FORTUNA_POOLS * SHA256_OUT_BYTES);
+        for i in 0..FORTUNA_POOLS {
+            if !self.reseed_count.is_multiple_of(1u64 << i) {
+                break;
+            }
+            let pool_digest = self.pools[i].finalize_reset();
+            material.extend_from_slice(&Sha256::digest(pool_digest));
+            self.pool_bytes[i] = 0;
+        }
+        self.reseed_generator(&material);
+    }
+
+    fn generate_blocks(&mut self, out: &mut [u8]) {
+        let mut cipher = Aes256CtrLe::new(&self.key.into(), &self.counter.to_le_bytes().into());
+        cipher.apply_keystream(out);
+        let blocks_used = out.len().div_ceil(AES_BLOCK_BYTES);
+        self.counter = self.counter.wrapping_add(blocks_used as u128);
+    }
+}
+
+impl Drbg for FortunaDrbg {
+    fn name(&self) -> &'static str {
+        "Fortuna DRBG"
+    }
+
+    fn key_bits(&self) -> Option<usize> {
+        Some(256)
+    }
+
+    fn block_bits(&self) -> Option<usize> {
+        Some(8 * AES_BLOCK_BYTES)
+    }
+
+    fn security_strength(&self) -> Option<usize> {
+        Some(256)
+    }
+
+    fn reseed(&mut self, seed: &[u8]) {
+        // Spread the seed over every pool, with enough events for pool 0 to trigger a reseed.
+        let events_per_pool = FORTUNA_MIN_POOL_BYTES.div_ceil(FORTUNA_EVENT_BYTES + 2);
+        let events = derive_material(
+            seed,
+            "fortuna-drbg",
+            FORTUNA_POOLS * events_per_pool * FORTUNA_EVENT_BYTES,
+        );
+        for (i, event) in events.chunks(FORTUNA_EVENT_BYTES).enumerate() {
+            self.add_random_event(0, i % FORTUNA_POOLS, event);
+        }
+    }
+
+    fn generate_bits(&mut self, bits: usize) -> BitString {
+        let byte_len = bits.div_ceil(8);
+        let mut bytes = vec![0u8; byte_len];
+
+        if self.pool_bytes[0] >= FORTUNA_MIN_POOL_BYTES {
+            self.reseed_from_pools();
+        }
+
+        for request in bytes.chunks_mut(FORTUNA_MAX_REQUEST_BYTES) {
+            self.generate_blocks(request);
+            let mut next_key = [0u8; 32];
+            self.generate_blocks(&mut next_key);
+            self.key = next_key;
+        }
+
+        BitString { bits, bytes }
+    }
+}
+
+/// Builds one generator from a seed; `None` where it is unsupported here.
+pub type Constructor = fn(&[u8]) -> Option<Box<dyn Drbg>>;
+
+macro_rules! constructor {
+    ($drbg:ty) => {
+        |seed| Some(Box::new(<$drbg>::new(seed)))
+    };
+}
+
+/// The constructor of every generator in the benchmark, in report order.
+pub fn constructors() -> Vec<Constructor> {
+    vec![
+        constructor!(ChaCha20Drbg),
+        constructor!(ChaCha12Drbg),
+        constructor!(ChaCha8Drbg),
+        constructor!(ChaCha20IetfDrbg),
+        constructor!(ChaCha20LegacyDrbg),
+        constructor!(AesCtrDrbg),
+        constructor!(Aes128CtrDrbg),
+        constructor!(Sm4CtrDrbg),
+        constructor!(SpeckCtrDrbg),
+        constructor!(SimonCtrDrbg),
+        constructor!(CamelliaCtrDrbg),
+        constructor!(TdesCtrDrbg),
+        constructor!(Blake3XofDrbg),
+        constructor!(Blake2bDrbg),
+        constructor!(Shake128Drbg),
+        constructor!(Shake256Drbg),
+        constructor!(KmacDrbg),
+        constructor!(KeccakDuplexDrbg),
+        constructor!(AsconXofDrbg),
+        constructor!(XdrbgShake256),
+        constructor!(XdrbgBlake3),
+        constructor!(HmacDrbg),
+        constructor!(HashDrbg),
+        constructor!(Sha3HashDrbg),
+        constructor!(FortunaDrbg),
+        constructor!(Hc256Drbg),
+        constructor!(RabbitDrbg),
+        constructor!(TriviumDrbg),
+        constructor!(Grain128AeadDrbg),
+        constructor!(IsaacDrbg),
+        constructor!(PhiloxDrbg),
+        constructor!(ThreefryDrbg),
+        constructor!(OsRandomDrbg),
+        constructor!(Xoshiro256StarStarDrbg),
+        constructor!(Pcg64Drbg),
+        constructor!(Mt19937Drbg),
+        constructor!(SplitMix64Drbg),
+        constructor!(Lcg128Drbg),
+        constructor!(MiddleSquareWeylDrbg),
+        constructor!(WyRandDrbg),
+        constructor!(RomuTrioDrbg),
+        constructor!(Rc4Drbg),
+        constructor!(DualEcDrbg),
+        // Hardware generators are skipped on CPUs without the instructions.
+        |seed| RdRandDrbg::new(seed).map(|d| Box::new(d) as Box<dyn Drbg>),
+        |seed| RdSeedDrbg::new(seed).map(|d| Box::new(d) as Box<dyn Drbg>),
+    ]
+}
+
+/// Every generator in the benchmark, seeded identically.
+pub fn build_generators(seed: &[u8]) -> Vec<Box<dyn Drbg>> {
+    constructors().iter().filter_map(|new| new(seed)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bytes(hex: &str) -> Vec<u8> {
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    const WORD_CIPHER_KEY: [u64; 4] = [
+        0x0706050403020100,
+        0x0f0e0d0c0b0a0908,
+        0x1716151413121110,
+        0x1f1e1d1c1b1a1918,
+    ];
+
+    #[test]
+    fn speck128_256_known_answer() {
+        let cipher = Speck128_256::from_key(WORD_CIPHER_KEY);
+        assert_eq!(
+            cipher.encrypt_words([0x65736f6874206e49, 0x202e72656e6f6f70]),
+            [0x4109010405c0f53e, 0x4eeeb48d9c188f43]
+        );
+    }
+
+    #[test]
+    fn simon128_256_known_answer() {
+        let cipher = Simon128_256::from_key(WORD_CIPHER_KEY);
+        assert_eq!(
+            cipher.encrypt_words([0x74206e69206d6f6f, 0x6d69732061207369]),
+            [0x8d2b5579afc8a3a0, 0x3bf72a87efe7b868]
+        );
+    }
+
+    /// eSTREAM test vectors, set 1, vector 0.
+    #[test]
+    fn trivium_known_answer() {
+        let mut key = [0u8; TRIVIUM_KEY_BYTES];
+        key[0] = 0x80;
+        let mut drbg = TriviumDrbg::from_key_iv(&key, &[0u8; TRIVIUM_KEY_BYTES]);
+        let keystream: Vec<u8> = (0..4).flat_map(|_| drbg.clock64().to_le_bytes()).collect();
+        assert_eq!(
+            keystream,
+            bytes("38eb86ff730d7a9caf8df13a4420540dbb7b651464c87501552041c249f29a64")
+        );
+    }
+
+    /// Random123 `kat_vectors`, philox4x32 10.
+    #[test]
+    fn philox4x32_10_known_answer() {
+        let cases = [
+            (
+                [0u32; 4],
+                [0u32; 2],
+                [0x6627e8d5, 0xe169c58d, 0xbc57ac4c, 0x9b00dbd8],
+            ),
+            (
+                [u32::MAX; 4],
+                [u32::MAX; 2],
+                [0x408f276d, 0x41c83b0e, 0xa20bc7c6, 0x6d5451fd],
+            ),
+            (
+                [0x243f6a88, 0x85a308d3, 0x13198a2e, 0x03707344],
+                [0xa4093822, 0x299f31d0],
+                [0xd16cfe09, 0x94fdcceb, 0x5001e420, 0x24126ea1],
+            ),
+        ];
+        for (counter, key, expected) in cases {
+            let drbg = PhiloxDrbg { key, counter: 0 };
+            let counter = counter
+                .iter()
+                .rev()
+                .fold(0u128, |acc, &word| (acc << 32) | word as u128);
+            assert_eq!(drbg.block(counter), expected);
+        }
+    }
+
+    /// Random123 `kat_vectors`, threefry4x64 20. The key of the third vector
+    /// repeats a word of the pi digits, as in the file.
+    #[test]
+    fn threefry4x64_20_known_answer() {
+        let cases = [
+            (
+                [0u64; 4],
+                [0u64; 4],
+                [
+                    0x09218ebde6c85537,
+                    0x55941f5266d86105,
+                    0x4bd25e16282434dc,
+                    0xee29ec846bd2e40b,
+                ],
+            ),
+            (
+                [u64::MAX; 4],
+                [u64::MAX; 4],
+                [
+                    0x29c24097942bba1b,
+                    0x0371bbfb0f6f4e11,
+                    0x3c231ffa33f83a1c,
+                    0xcd29113fde32d168,
+                ],
+            ),
+            (
+                [
+                    0x243f6a8885a308d3,
+                    0x13198a2e03707344,
+                    0xa4093822299f31d0,
+                    0x082efa98ec4e6c89,
+                ],
+                [
+                    0x452821e638d01377,
+                    0xbe5466cf34e90c6c,
+                    0xbe5466cf34e90c6c,
+                    0xc0ac29b7c97c50dd,
+                ],
+                [
+                    0xa7e8fde591651bd9,
+                    0xbaafd0c30138319b,
+                    0x84a5c1a729e685b9,
+                    0x901d406ccebc1ba4,
+                ],
+            ),
+        ];
+        for (counter, key, expected) in cases {
+            let drbg = ThreefryDrbg {
+                schedule: ThreefryDrbg::key_schedule(key),
+                counter: 0,
+            };
+            assert_eq!(drbg.block(counter), expected);
+        }
+    }
+
+    /// CAVP HMAC_DRBG.rsp, [SHA-256] without prediction resistance or
+    /// personalization string, COUNT = 0: the second of two 1024-bit requests.
+    #[test]
+    fn hmac_drbg_known_answer() {
+        let mut seed_material =
+            bytes("ca851911349384bffe89de1cbdc46e6831e44d34a4fb935ee285dd14b71a7488");
+        seed_material.extend(bytes("659ba96c601dc69fc902940805ec0ca8"));
+        let mut drbg = HmacDrbg::instantiate(&seed_material);
+        drbg.generate_bits(1024);
+        assert_eq!(
+            drbg.generate_bits(1024).bytes,
+            bytes(concat!(
+                "e528e9abf2dece54d47c7e75e5fe302149f817ea9fb4bee6f4199697d04d5b89",
+                "d54fbb978a15b5c443c9ec21036d2460b6f73ebad0dc2aba6e624abf07745bc1",
+                "07694bb7547bb0995f70de25d6b29e2d3011bb19d27676c07162c8b5ccde0668",
+                "961df86803482cb37ed6d5c0bb8d50cf1f50d476aa0458bdaba806f48be9dcb8",
+            ))
+        );
+    }
+
+    /// CAVP CTR_DRBG.rsp, [AES-256 use df] without prediction resistance or
+    /// personalization string, COUNT = 0: the second of two 512-bit requests.
+    #[test]
+    fn aes256_ctr_drbg_known_answer() {
+        let mut seed_material =
+            bytes("36401940fa8b1fba91a1661f211d78a0b9389a74e5bccfece8d766af1a6d3b14");
+        seed_material.extend(bytes("496f25b0f1301b4f501be30380a137eb"));
+        let mut drbg = AesCtrDrbg::instantiate(&seed_material);
+        drbg.generate_bits(512);
+        assert_eq!(
+            drbg.generate_bits(512).bytes,
+            bytes(concat!(
+                "5862eb38bd558dd978a696e6df164782ddd887e7e9a6c9f3f1fbafb78941b535",
+                "a64912dfd224c6dc7454e5250b3d97165e16260c2faf1cc7735cb75fb4f07e1d",
+            ))
+        );
+    }
+
+    /// As above for [AES-128 use df], which also covers the SM4 instance of
+    /// the same macro.
+    #[test]
+    fn aes128_ctr_drbg_known_answer() {
+        let mut seed_material = bytes("890eb067acf7382eff80b0c73bc872c6");
+        seed_material.extend(bytes("aad471ef3ef1d203"));
+        let mut drbg = Aes128CtrDrbg::instantiate(&seed_material);
+        drbg.generate_bits(512);
+        assert_eq!(
+            drbg.generate_bits(512).bytes,
+            bytes(concat!(
+                "a5514ed7095f64f3d0d3a5760394ab42062f373a25072a6ea6bcfd8489e94af6",
+                "cf18659fea22ed1ca0a9e33f718b115ee536b12809c31b72b08ddd8be1910fa3",
+            ))
+        );
+    }
+
+    const HASH_DRBG_ENTROPY: &str =
+        "6b50a7d8f8a55d7a3df8bb40bcc3b722d8708de67fda010b03c4c84d72096f8c";
+    const HASH_DRBG_NONCE: &str = "3ec649cc6256d9fa31db7a2904aaf025";
+
+    /// CAVP Hash_DRBG.rsp, [SHA-512] without prediction resistance or
+    /// personalization string, COUNT = 0: the second of two 2048-bit requests.
+    #[test]
+    fn hash_drbg_known_answer() {
+        let mut seed_material = bytes(HASH_DRBG_ENTROPY);
+        seed_material.extend(bytes(HASH_DRBG_NONCE));
+        let mut drbg = HashDrbg::instantiate(&seed_material);
+        drbg.generate_bits(2048);
+        assert_eq!(
+            drbg.generate_bits(2048).bytes,
+            bytes(concat!(
+                "95b7f17e9802d3577392c6a9c08083b67dd1292265b5f42d237f1c55bb9b10bf",
+                "cfd82c77a378b8266a0099143b3c2d64611eeeb69acdc055957c139e8b190c7a",
+                "06955f2c797c2778de940396a501f40e91396acf8d7e45ebdbb53bbf8c975230",
+                "d2f0ff9106c76119ae498e7fbc03d90f8e4c51627aed5c8d4263d5d2b978873a",
+                "0de596ee6dc7f7c29e37eee8b34c90dd1cf6a9ddb22b4cbd086b14b35de93da2",
+                "d5cb1806698cbd7bbb67bfe3d31fd2d1dbd2a1e058a3eb99d7e51f1a938eed5e",
+                "1c1de23a6b4345d3191409f92f39b3670d8dbfb635d8e6a36932d81033d1448d",
+                "63b403ddf88e121b6e819ac381226c1321e4b08644f6727c368c5a9f7a4b3ee2",
+            ))
+        );
+    }
+
+    /// CAVP has no SHA3 Hash_DRBG vectors: the same procedure on the SHA-512
+    /// inputs, with 1024-bit requests, as computed by an independent
+    /// implementation of section 10.1.1.
+    #[test]
+    fn sha3_hash_drbg_regression() {
+        let mut seed_material = bytes(HASH_DRBG_ENTROPY);
+        seed_material.extend(bytes(HASH_DRBG_NONCE));
+        let mut drbg = Sha3HashDrbg::instantiate(&seed_material);
+        drbg.generate_bits(1024);
+        assert_eq!(
+            drbg.generate_bits(1024).bytes,
+            bytes(concat!(
+                "c353010949b4af5579a965080d8aa50dd93a4c0e964b74cfd38a65e963a9b3e6",
+                "1f5f9cb4d3c5d6fd18c53023585ced77cdd328365e026a5705605fdadee9113f",
+                "482c97cbe183f2e7df7d5fc03c4e51753a92635e1fa199894166fd115090df63",
+                "4f14fb867b3fe51f65a625ecf39b21e7806cf3d77d7ba6cb4348747d2153eccb",
+            ))
+        );
+    }
+
+    /// Test vectors of the Grain-128AEADv2 specification: the register
+    /// states after initialization, and the ciphertext of the second vector,
+    /// whose 8 plaintext bytes are encrypted with the keystream following
+    /// the 9 bytes of length-prefixed associated data.
+    #[test]
+    fn grain128aead_known_answer() {
+        let state = |hex: &str| u128::from_le_bytes(bytes(hex).try_into().unwrap());
+
+        let drbg = Grain128AeadDrbg::with_key(0, 0);
+        assert_eq!(drbg.lfsr, state("8f395a9421b0963364e2ed30679c8ee1"));
+        assert_eq!(drbg.nfsr, state("81f7e0c655d035823310c278438dbc20"));
+
+        let mut drbg = Grain128AeadDrbg::with_key(
+            state("000102030405060708090a0b0c0d0e0f"),
+            state("000102030405060708090a0b00000000"),
+        );
+        assert_eq!(drbg.lfsr, state("0e1f950d45e05087c4cd63fd00eab310"));
+        assert_eq!(drbg.nfsr, state("b3c2e1b1eec1f08c2d6eae957f6af9d0"));
+        let keystream = drbg.generate_bits(8 * 17).bytes;
+        let ciphertext: Vec<u8> = keystream[9..]
+            .iter()
+            .zip(0u8..)
+            .map(|(z, m)| z ^ m)
+            .collect();
+        assert_eq!(ciphertext, bytes("96d1bda7ae11f0ba"));
+    }
+
+    /// NIST SP 800-185 example values, KMAC samples #4 to #6.
+    #[test]
+    fn kmac256_known_answer() {
+        let key: Vec<u8> = (0x40..0x60).collect();
+        let tagged = b"My Tagged Application".as_slice();
+        let cases = [
+            (
+                vec![0, 1, 2, 3],
+                tagged,
+                concat!(
+                    "20c570c31346f703c9ac36c61c03cb64c3970d0cfc787e9b79599d273a68d2f7",
+                    "f69d4cc3de9d104a351689f27cf6f5951f0103f33f4f24871024d9c27773a8dd",
+                ),
+            ),
+            (
+                (0..200).collect(),
+                b"".as_slice(),
+                concat!(
+                    "75358cf39e41494e949707927cee0af20a3ff553904c86b08f21cc414bcfd691",
+                    "589d27cf5e15369cbbff8b9a4c2eb17800855d0235ff635da82533ec6b759b69",
+                ),
+            ),
+            (
+                (0..200).collect(),
+                tagged,
+                concat!(
+                    "b58618f71f92e1d56c1b8c55ddd7cd188b97b4ca4d99831eb2699a837da2e4d9",
+                    "70fbacfde50033aea585f1a2708510c32d07880801bd182898fe476876fc8965",
+                ),
+            ),
+        ];
+        for (data, customization, expected) in cases {
+            let keyed = KmacDrbg::absorb_key(&key, customization);
+            let mut out = [0u8; 64];
+            KmacDrbg::kmac(&keyed, &data, &mut out);
+            assert_eq!(out.to_vec(), bytes(expected));
+        }
+    }
+
+    /// Instantiate, reseed and two 1024-bit requests of XDRBG-SHAKE256 with
+    /// seed material 00..3f and 40..7f, against an independent implementation
+    /// of the paper's encode(S, α, n) with empty α.
+    #[test]
+    fn xdrbg_shake256_regression() {
+        let mut drbg = XdrbgShake256::instantiate(&(0..64).collect::<Vec<u8>>());
+        drbg.reseed_from(&(64..128).collect::<Vec<u8>>());
+        drbg.generate_bits(1024);
+        assert_eq!(
+            drbg.generate_bits(1024).bytes,
+            bytes(concat!(
+                "347d707c13ce2bf2eab0ec3f689bcde77156da93657b7f4072fbde0ca7e545a8",
+                "1274e7bd456a436d5cb9441515757b61c7bd608bb6ec43f75d658162b0452ce8",
+                "9f5f44d2400af79cd546c82e2d083eb753e8c8f879a9afd62bdd1276a065817e",
+                "561b303fa9ef35ec22ac5c4e59fe380639c0042e5df993cdc06248268cbb01c4",
+            ))
+        );
+    }
+
+    #[test]
+    fn supports_parallel_matches_the_parallel_path() {
+        for mut drbg in build_generators(b"parallel probe") {
+            let supported = drbg.supports_parallel();
+            assert_eq!(
+                drbg.generate_bits_parallel(1_024).is_some(),
+                supported,
+                "{}",
+                drbg.name()
+            );
+        }
+    }
+}
@@ -0,0 +1,429 @@
+use crate::drbg::BitString;
+use serde::{Deserialize, Serialize};
+
+// Upper 99% bound used by every SP 800-90B estimator.
+const Z_ALPHA: f64 = 2.576;
+// Bits per symbol: the symbol-level estimators run on bytes.
+const SYMBOL_BITS: f64 = 8.0;
+// SP 800-90B §6.3.5: t-tuples are used while some tuple occurs this often.
+const T_TUPLE_MIN_COUNT: u64 = 35;
+// SP 800-90B §6.3.3: length of the most likely sequence in the Markov estimate.
+const MARKOV_SEQUENCE_LEN: i32 = 128;
+// SP 800-90B §6.3.4: block size b, dictionary size d and constant c.
+const COMPRESSION_BLOCK_BITS: usize = 6;
+const COMPRESSION_DICTIONARY: usize = 1000;
+const COMPRESSION_C: f64 = 0.5907;
+const COMPRESSION_ITERATIONS: usize = 40;
+
+/// Min-entropy per byte from the SP 800-90B non-IID estimators. The binary
+/// estimators (collision, Markov, compression) run on the bit string and are
+/// scaled by 8, as in the NIST assessment tool. `None` where the sequence is
+/// too short or too regular for the estimator to be defined.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct MinEntropyEstimates {
+    pub most_common_value: f64,
+    pub collision: f64,
+    pub markov: f64,
+    pub compression: Option<f64>,
+    pub t_tuple: Option<f64>,
+    pub lrs: Option<f64>,
+}
+
+impl MinEntropyEstimates {
+    /// The assessed min-entropy: the lowest of all applicable estimates.
+    pub fn min(&self) -> f64 {
+        [self.compression, self.t_tuple, self.lrs]
+            .into_iter()
+            .flatten()
+            .fold(
+                self.most_common_value.min(self.collision).min(self.markov),
+                f64::min,
+            )
+    }
+}
+
+/// Runs all estimators on the whole bytes of `bits`.
+pub fn estimate(bits: &BitString) -> MinEntropyEstimates {
+    let bytes = &bits.bytes[..bits.bits / 8];
+    let (t_tuple, lrs) = tuple_estimates(bytes);
+    MinEntropyEstimates {
+        most_common_value: most_common_value(bytes),
+        collision: SYMBOL_BITS * collision(bits),
+        markov: SYMBOL_BITS * markov(bits),
+        compression: compression(bits).map(|h| SYMBOL_BITS * h),
+        t_tuple,
+        lrs,
+    }
+}
+
+/// -log2 of the upper confidence bound on a probability estimated from `n` samples.
+fn upper_bound_entropy(p: f64, n: usize) -> f64 {
+    let bound = p + Z_ALPHA * (p * (1.0 - p) / (n as f64 - 1.0)).sqrt();
+    -bound.min(1.0).log2()
+}
+
+/// SP 800-90B §6.3.1 most common value estimate.
+fn most_common_value(bytes: &[u8]) -> f64 {
+    if bytes.len() < 2 {
+        return SYMBOL_BITS;
+    }
+    let mut counts = [0u64; 256];
+    for &b in bytes {
+        counts[b as usize] += 1;
+    }
+    let max = *counts.iter().max().unwrap();
+    upper_bound_entropy(max as f64 / bytes.len() as f64, bytes.len())
+}
+
+/// SP 800-90B §6.3.2 collision estimate, per bit. For binary data a collision
+/// takes either 2 or 3 samples, so E[t] = 2 + 2p(1 - p) is solved in closed form.
+fn collision(bits: &BitString) -> f64 {
+    let n = bits.bits;
+    let mut times = Vec::new();
+    let mut i = 0;
+    while i + 1 < n {
+        let t = if bits.bit(i) == bits.bit(i + 1) {
+            2
+        } else if i + 2 < n {
+            3
+        } else {
+            break;
+        };
+        times.push(t as f64);
+        i += t;
+    }
+    let v = times.len();
+    if v < 2 {
+        return 1.0;
+    }
+
+    let mean = times.iter().sum::<f64>() / v as f64;
+    let variance = times.iter().map(|t| (t - mean).powi(2)).sum::<f64>() / (v as f64 - 1.0);
+    let mean_lower = mean - Z_ALPHA * variance.sqrt() / (v as f64).sqrt();
+    let p = if mean_lower < 2.0 {
+        1.0
+    } else if mean_lower >= 2.5 {
+        0.5
+    } else {
+        0.5 + (1.25 - 0.5 * mean_lower).sqrt()
+    };
+    -p.log2()
+}
+
+/// SP 800-90B §6.3.3 Markov estimate, per bit, from the most likely of the
+/// six candidate 128-bit sequences under the first-order transition model.
+fn markov(bits: &BitString) -> f64 {
+    let n = bits.bits;
+    if n < 2 {
+        return 1.0;
+    }
+    let mut ones = 0u64;
+    let mut transitions = [[0u64; 2]; 2];
+    let mut previous = bits.bit(0);
+    ones += previous as u64;
+    for i in 1..n {
+        let bit = bits.bit(i);
+        ones += bit as u64;
+        transitions[previous as usize][bit as usize] += 1;
+        previous = bit;
+    }
+
+    let p1 = ones as f64 / n as f64;
+    let p0 = 1.0 - p1;
+    let transition = |from: usize, to: usize| {
+        let total = transitions[from][0] + transitions[from][1];
+        if total == 0 {
+            0.0
+        } else {
+            transitions[from][to] as f64 / total as f64
+        }
+    };
+    let (p00, p01) = (transition(0, 0), transition(0, 1));
+    let (p10, p11) = (transition(1, 0), transition(1, 1));
+    let k = MARKOV_SEQUENCE_LEN;
+    let candidates = [
+        p0 * p00.powi(k - 1),
+        p0 * p01.powi(k / 2) * p10.powi(k / 2 - 1),
+        p0 * p01 * p11.powi(k - 2),
+        p1 * p10 * p00.powi(k - 2),
+        p1 * p10.powi(k / 2) * p01.powi(k / 2 - 1),
+        p1 * p11.powi(k - 1),
+    ];
+    let p_max = candidates.into_iter().fold(0.0, f64::max);
+    (-p_max.log2() / k as f64).min(1.0)
+}
+
+/// SP 800-90B §6.3.4 compression (Maurer-style) estimate, per bit, over 6-bit
+/// blocks with a 1000-block dictionary.
+fn compression(bits: &BitString) -> Option<f64> {
+    let b = COMPRESSION_BLOCK_BITS;
+    let d = COMPRESSION_DICTIONARY;
+    let blocks = bits.bits / b;
+    if blocks <= d + 1 {
+        return None;
+    }
+    let block = |i: usize| (0..b).fold(0usize, |acc, j| (acc << 1) | bits.bit(i * b + j) as usize);
+
+    let mut last_seen = vec![0usize; 1 << b];
+    for i in 0..d {
+        last_seen[block(i)] = i + 1;
+    }
+    let mut sum = 0.0;
+    let mut sum_squares = 0.0;
+    for i in d..blocks {
+        let value = block(i);
+        let distance = match last_seen[value] {
+            0 => i + 1,
+            seen => i + 1 - seen,
+        };
+        last_seen[value] = i + 1;
+        let log = (distance as f64).log2();
+        sum += log;
+        sum_squares += log * log;
+    }
+
+    let nu = (blocks - d) as f64;
+    let mean = sum / nu;
+    let sigma = COMPRESSION_C * (sum_squares / (nu - 1.0) - mean * mean).max(0.0).sqrt();
+    let mean_lower = mean - Z_ALPHA * sigma / nu.sqrt();
+
+    // X(p) = G(p) + (2^b - 1)·G(q) decreases in p; bisect on [2^-b, 1].
+    let symbols = (1usize << b) as f64;
+    let logs: Vec<f64> = (1..=blocks).map(|t| (t as f64).log2()).collect();
+    let expected = |p: f64| {
+        let q = (1.0 - p) / (symbols - 1.0);
+        compression_g(p, d, &logs) + (symbols - 1.0) * compression_g(q, d, &logs)
+    };
+    let (mut low, mut high) = (1.0 / symbols, 1.0);
+    if expected(low) < mean_lower {
+        return Some(1.0);
+    }
+    for _ in 0..COMPRESSION_ITERATIONS {
+        let mid = 0.5 * (low + high);
+        if expected(mid) > mean_lower {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    Some(-low.log2() / b as f64)
+}
+
+/// G(z) from SP 800-90B §6.3.4 step 7, computed with a running prefix sum
+/// over u so each evaluation is linear in the number of blocks; `logs[t - 1]`
+/// holds log2(t).
+fn compression_g(z: f64, d: usize, logs: &[f64]) -> f64 {
+    let mut total = 0.0;
+    // Σ_{u=1}^{t-1} log2(u)·z²(1-z)^(u-1), extended one term per t.
+    let mut prefix = 0.0;
+    let mut power = 1.0; // (1-z)^(t-1)
+    for (t, &log_t) in (1..).zip(logs) {
+        // Once (1-z)^(t-1) underflows only the prefix remains; stopping here
+        // also avoids crawling through subnormals.
+        if power < f64::MIN_POSITIVE {
+            total += prefix * (logs.len() - (t - 1).max(d)) as f64;
+            break;
+        }
+        if t > d {
+            total += prefix + log_t * z * power;
+        }
+        prefix += log_t * z * z * power;
+        power *= 1.0 - z;
+    }
+    total / (logs.len() - d) as f64
+}
+
+/// SP 800-90B §6.3.5 t-tuple and §6.3.6 longest repeated substring estimates.
+/// Both look at the tuple counts of increasing width, so they share one pass
+/// per width; the widths stop once no tuple repeats.
+fn tuple_estimates(bytes: &[u8]) -> (Option<f64>, Option<f64>) {
+    let len = bytes.len();
+    if len < 2 {
+        return (None, None);
+    }
+    let mut t_tuple_max: Option<f64> = None;
+    let mut lrs_max: Option<f64> = None;
+    for width in 1..len {
+        let (max_count, pairs) = tuple_counts(bytes, width);
+        if max_count < 2 {
+            break;
+        }
+        let windows = (len - width + 1) as f64;
+        let exponent = 1.0 / width as f64;
+        let p = if max_count >= T_TUPLE_MIN_COUNT {
+            (max_count as f64 / windows).powf(exponent)
+        } else {
+            (pairs / (windows * (windows - 1.0) / 2.0)).powf(exponent)
+        };
+        let slot = if max_count >= T_TUPLE_MIN_COUNT {
+            &mut t_tuple_max
+        } else {
+            &mut lrs_max
+        };
+        *slot = Some(slot.map_or(p, |q| q.max(p)));
+    }
+    let h = |p: Option<f64>| p.map(|p| upper_bound_entropy(p, len));
+    (h(t_tuple_max), h(lrs_max))
+}
+
+/// Highest occurrence count among the `width`-byte tuples, and Σ C(count, 2).
+fn tuple_counts(bytes: &[u8], width: usize) -> (u64, f64) {
+    // Tuples of up to 8 bytes are packed into words, which sort much faster.
+    if width <= 8 {
+        let mut keys: Vec<u64> = bytes
+            .windows(width)
+            .map(|w| w.iter().fold(0u64, |acc, &b| (acc << 8) | b as u64))
+            .collect();
+        keys.sort_unstable();
+        run_lengths(&keys)
+    } else {
+        let mut keys: Vec<&[u8]> = bytes.windows(width).collect();
+        keys.sort_unstable();
+        run_lengths(&keys)
+    }
+}
+
+fn run_lengths<T: PartialEq>(sorted: &[T]) -> (u64, f64) {
+    let mut max_count = 0u64;
+    let mut pairs = 0.0;
+    for group in sorted.chunk_by(|a, b| a == b) {
+        let count = group.len() as u64;
+        max_count = max_count.max(count);
+        pairs += (count * (count - 1) / 2) as f64;
+    }
+    (max_count, pairs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn bit_string(bytes: Vec<u8>) -> BitString {
+        BitString {
+            bits: 8 * bytes.len(),
+            bytes,
+        }
+    }
+
+    /// Symbols from {0, 1, 2, 3} by a fixed LCG, so that short tuples are
+    /// common (t-tuple) and longer ones rare (LRS).
+    fn four_symbol_bytes(len: usize) -> Vec<u8> {
+        let mut state = 1u64;
+        (0..len)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6_364_136_223_846_793_005)
+                    .wrapping_add(1_442_695_040_888_963_407);
+                (state >> 62) as u8
+            })
+            .collect()
+    }
+
+    #[test]
+    fn constant_input_has_no_entropy() {
+        let estimates = estimate(&bit_string(vec![0x00; 1024]));
+        for h in [
+            estimates.most_common_value,
+            estimates.collision,
+            estimates.markov,
+            estimates.compression.unwrap(),
+            estimates.t_tuple.unwrap(),
+        ] {
+            assert!(h < 1e-6, "{estimates:?}");
+        }
+    }
+
+    /// 0101…: every transition is certain, so the most likely 128-bit
+    /// sequence only pays for its first bit.
+    #[test]
+    fn alternating_input_has_markov_estimate_near_zero() {
+        let bits = bit_string(vec![0x55; 1024]);
+        assert!((markov(&bits) - 1.0 / MARKOV_SEQUENCE_LEN as f64).abs() < 1e-12);
+    }
+
+    /// Mode 1 with 8 of 20 samples: p_u = 0.4 + 2.576·√(0.4·0.6/19).
+    #[test]
+    fn most_common_value_by_hand() {
+        let bytes = [0, 1, 1, 2, 0, 1, 2, 2, 0, 1, 0, 1, 1, 0, 2, 2, 1, 0, 2, 1];
+        let p_u = 0.4 + 2.576 * (0.4f64 * 0.6 / 19.0).sqrt();
+        assert!((most_common_value(&bytes) - -p_u.log2()).abs() < 1e-12);
+        assert!((most_common_value(&bytes) - 0.536).abs() < 5e-4);
+    }
+
+    /// "00" then "010", repeated: collision times alternate 2 and 3, and the
+    /// closed form must solve 2 + 2p(1 - p) = the lower bound on their mean.
+    #[test]
+    fn collision_closed_form_solves_expected_time() {
+        let text = "00010".repeat(400);
+        let mut bytes = vec![0u8; text.len() / 8];
+        for (i, c) in text.bytes().enumerate() {
+            bytes[i / 8] |= (c - b'0') << (7 - i % 8);
+        }
+        let h = collision(&bit_string(bytes));
+
+        let v = 800.0f64;
+        let variance = 0.25 * v / (v - 1.0);
+        let mean_lower = 2.5 - Z_ALPHA * variance.sqrt() / v.sqrt();
+        let p = 2f64.powf(-h);
+        assert!(p > 0.5 && p < 1.0);
+        assert!((2.0 + 2.0 * p * (1.0 - p) - mean_lower).abs() < 1e-12);
+    }
+
+    /// The running-prefix `compression_g` against G(z) summed term by term,
+    /// including a z large enough to take the underflow shortcut.
+    #[test]
+    fn compression_g_matches_direct_sum() {
+        let (d, blocks) = (COMPRESSION_DICTIONARY, 2000);
+        let logs: Vec<f64> = (1..=blocks).map(|t| (t as f64).log2()).collect();
+        let direct = |z: f64| {
+            let mut total = 0.0;
+            for t in d + 1..=blocks {
+                for u in 1..=t {
+                    let f = if u < t {
+                        z * z * (1.0 - z).powi(u as i32 - 1)
+                    } else {
+                        z * (1.0 - z).powi(t as i32 - 1)
+                    };
+                    total += logs[u - 1] * f;
+                }
+            }
+            total / (blocks - d) as f64
+        };
+        for z in [1.0 / 64.0, 0.1, 0.5, 0.9] {
+            let fast = compression_g(z, d, &logs);
+            assert!((fast - direct(z)).abs() < 1e-9 * fast.max(1.0), "z = {z}");
+        }
+    }
+
+    /// `tuple_estimates` against tuple counts taken with a hash map: widths
+    /// whose most common tuple occurs at least 35 times feed the t-tuple
+    /// estimate, the rest the LRS estimate.
+    #[test]
+    fn tuple_estimates_split_at_the_count_cutoff() {
+        let bytes = four_symbol_bytes(2000);
+        let len = bytes.len();
+        let (mut t_tuple, mut lrs) = (0.0f64, 0.0f64);
+        for width in 1..len {
+            let mut counts: HashMap<&[u8], u64> = HashMap::new();
+            for window in bytes.windows(width) {
+                *counts.entry(window).or_default() += 1;
+            }
+            let max_count = *counts.values().max().unwrap();
+            if max_count < 2 {
+                break;
+            }
+            let windows = (len - width + 1) as f64;
+            if max_count >= T_TUPLE_MIN_COUNT {
+                t_tuple = t_tuple.max((max_count as f64 / windows).powf(1.0 / width as f64));
+            } else {
+                let pairs: f64 = counts.values().map(|&c| (c * (c - 1) / 2) as f64).sum();
+                let p = pairs / (windows * (windows - 1.0) / 2.0);
+                lrs = lrs.max(p.powf(1.0 / width as f64));
+            }
+        }
+        let (t_tuple_h, lrs_h) = tuple_estimates(&bytes);
+        assert!((t_tuple_h.unwrap() - upper_bound_entropy(t_tuple, len)).abs() < 1e-12);
+        assert!((lrs_h.unwrap() - upper_bound_entropy(lrs, len)).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,176 @@
+//! Host state that moves timings between runs: the core the benchmark is
+//! pinned to, and the frequency governor and turbo setting of that core.
+//! Both are Linux only; elsewhere pinning fails and the state reads as
+//! unknown. [`Environment`] adds the hardware and build the timings came
+//! from, for `environment.json`.
+
+use crate::config::to_hex;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::error::Error;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::Path;
+use std::thread;
+
+const CPU_DIR: &str = "/sys/devices/system/cpu";
+
+/// Frequency scaling state of one core; `None` where sysfs does not say.
+pub struct FrequencyState {
+    pub governor: Option<String>,
+    pub turbo: Option<bool>,
+}
+
+/// Everything a sweep's timings depend on besides the code under test.
+#[derive(Serialize)]
+pub struct Environment {
+    /// First 16 hex digits of the SHA-256 of the other fields as JSON; the
+    /// `environment` of every record measured here.
+    pub id: String,
+    pub cpu_model: Option<String>,
+    /// Logical CPUs available to the process.
+    pub cpus: usize,
+    pub pinned_core: Option<usize>,
+    pub scaling_governor: Option<String>,
+    pub turbo: Option<bool>,
+    pub os: &'static str,
+    /// Kernel release; Linux only.
+    pub os_release: Option<String>,
+    pub arch: &'static str,
+    pub rustc: Option<&'static str>,
+    pub git_commit: Option<&'static str>,
+    /// Detected CPU features the generators' fast paths use.
+    pub cpu_features: Vec<&'static str>,
+}
+
+fn read_trimmed(path: &str) -> Option<String> {
+    Some(fs::read_to_string(path).ok()?.trim().to_string())
+}
+
+/// Restricts the calling thread to `core`. Threads it spawns afterwards
+/// inherit the restriction; threads that already exist do not.
+#[cfg(target_os = "linux")]
+pub fn pin_to_core(core: usize) -> io::Result<()> {
+    // SAFETY: `set` is a plain bitmask, zeroed and filled through the libc
+    // helpers, and outlives the call that reads it.
+    unsafe {
+        let mut set: libc::cpu_set_t = std::mem::zeroed();
+        if core >= 8 * size_of::<libc::cpu_set_t>() {
+            return Err(io::ErrorKind::InvalidInput.into());
+        }
+        libc::CPU_SET(core, &mut set);
+        if libc::sched_setaffinity(0, size_of::<libc::cpu_set_t>(), &set) != 0 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn pin_to_core(_core: usize) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "CPU pinning is Linux only",
+    ))
+}
+
+pub fn frequency_state(core: usize) -> FrequencyState {
+    let governor = read_trimmed(&format!("{CPU_DIR}/cpu{core}/cpufreq/scaling_governor"));
+    // intel_pstate reports the inverse; acpi-cpufreq and amd-pstate use `boost`.
+    let turbo = match read_trimmed(&format!("{CPU_DIR}/intel_pstate/no_turbo")) {
+        Some(no_turbo) => Some(no_turbo == "0"),
+        None => read_trimmed(&format!("{CPU_DIR}/cpufreq/boost")).map(|boost| boost == "1"),
+    };
+    FrequencyState { governor, turbo }
+}
+
+fn cpu_model() -> Option<String> {
+    let cpuinfo = fs::read_to_string("/proc/cpuinfo").ok()?;
+    let line = cpuinfo.lines().find(|l| l.starts_with("model name"))?;
+    Some(line.split_once(':')?.1.trim().to_string())
+}
+
+#[allow(unused_mut)]
+fn cpu_features() -> Vec<&'static str> {
+    let mut features = Vec::new();
+    #[cfg(target_arch = "x86_64")]
+    {
+        macro_rules! detect {
+            ($($feature:tt),*) => {
+                $(if std::arch::is_x86_feature_detected!($feature) {
+                    features.push($feature);
+                })*
+            };
+        }
+        detect!(
+            "aes",
+            "pclmulqdq",
+            "sha",
+            "avx2",
+            "avx512f",
+            "rdrand",
+            "rdseed"
+        );
+    }
+    #[cfg(target_arch = "aarch64")]
+    {
+        macro_rules! detect {
+            ($($feature:tt),*) => {
+                $(if std::arch::is_aarch64_feature_detected!($feature) {
+                    features.push($feature);
+                })*
+            };
+        }
+        detect!("neon", "aes", "sha2", "sha3", "rand");
+    }
+    features
+}
+
+pub fn environment(pinned_core: Option<usize>, state: &FrequencyState) -> Environment {
+    let mut environment = Environment {
+        id: String::new(),
+        cpu_model: cpu_model(),
+        cpus: thread::available_parallelism().map_or(1, |n| n.get()),
+        pinned_core,
+        scaling_governor: state.governor.clone(),
+        turbo: state.turbo,
+        os: std::env::consts::OS,
+        os_release: read_trimmed("/proc/sys/kernel/osrelease"),
+        arch: std::env::consts::ARCH,
+        rustc: option_env!("BUILD_RUSTC_VERSION"),
+        git_commit: option_env!("BUILD_GIT_COMMIT"),
+        cpu_features: cpu_features(),
+    };
+    let json = serde_json::to_vec(&environment).expect("serializable");
+    environment.id = to_hex(&Sha256::digest(json)[..8]);
+    environment
+}
+
+/// Writes `metadata.csv` as `key,value` rows, with the base seed in hex;
+/// unknown values are left empty.
+pub fn write_metadata(
+    dir: &Path,
+    seed: &[u8],
+    pinned_core: Option<usize>,
+    state: &FrequencyState,
+) -> Result<(), Box<dyn Error>> {
+    let mut file = File::create(dir.join("metadata.csv"))?;
+    writeln!(file, "key,value")?;
+    writeln!(file, "seed,{}", to_hex(seed))?;
+    writeln!(
+        file,
+        "pinned_core,{}",
+        pinned_core.map(|c| c.to_string()).unwrap_or_default()
+    )?;
+    writeln!(
+        file,
+        "scaling_governor,{}",
+        state.governor.as_deref().unwrap_or_default()
+    )?;
+    writeln!(
+        file,
+        "turbo,{}",
+        state.turbo.map(|t| t.to_string()).unwrap_or_default()
+    )?;
+    Ok(())
+}
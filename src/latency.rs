@@ -0,0 +1,104 @@
+//! Small-request latency benchmark: every generator serves a long series of
+//! tiny requests, each timed on its own, so per-call overhead (allocation,
+//! state updates, reseed bookkeeping) shows up instead of bulk throughput.
+
+use crate::config::Config;
+use random_numbers::drbg::Drbg;
+use std::error::Error;
+use std::fs::File;
+use std::io::Write;
+use std::iter;
+use std::path::Path;
+use std::time::Instant;
+
+const REQUEST_BITS: [usize; 2] = [128, 256];
+const CALLS: usize = 1_000_000;
+// Series stop early once the timed calls add up to this, which only the
+// slowest generators (Dual_EC_DRBG, 3DES) reach.
+const SERIES_BUDGET_NS: u64 = 10_000_000_000;
+// Untimed calls before each series.
+const WARMUP_CALLS: usize = 1_000;
+
+pub struct LatencySample {
+    pub generator: String,
+    pub cryptographic: bool,
+    pub request_bits: usize,
+    /// Per-call latencies in nanoseconds, sorted ascending.
+    pub latencies_ns: Vec<u64>,
+}
+
+/// Nearest-rank `q` quantile of ascending `sorted`.
+pub fn quantile(sorted: &[u64], q: f64) -> u64 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let rank = (q * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Times one `generate_bits` call per requested size, in nanoseconds, until
+/// the sizes run out or the calls add up to `SERIES_BUDGET_NS`.
+pub fn time_requests(drbg: &mut dyn Drbg, request_bits: impl Iterator<Item = usize>) -> Vec<u64> {
+    let mut latencies_ns = Vec::with_capacity(request_bits.size_hint().0);
+    let mut total_ns = 0;
+    for bits in request_bits {
+        if total_ns >= SERIES_BUDGET_NS {
+            break;
+        }
+        let start = Instant::now();
+        drbg.generate_bits(bits);
+        let elapsed = start.elapsed().as_nanos() as u64;
+        latencies_ns.push(elapsed);
+        total_ns += elapsed;
+    }
+    latencies_ns
+}
+
+/// Runs up to `CALLS` timed requests per generator and request size. The
+/// reported latencies include the cost of reading the clock (a few tens of ns).
+pub fn run(config: &Config) -> Vec<LatencySample> {
+    let mut samples = Vec::new();
+    for &request_bits in REQUEST_BITS.iter() {
+        for mut drbg in config.build_generators(&config.seed_for(0, request_bits)) {
+            for _ in 0..WARMUP_CALLS {
+                drbg.generate_bits(request_bits);
+            }
+            let mut latencies_ns =
+                time_requests(drbg.as_mut(), iter::repeat_n(request_bits, CALLS));
+            latencies_ns.sort_unstable();
+            samples.push(LatencySample {
+                generator: drbg.name().to_string(),
+                cryptographic: drbg.is_cryptographic(),
+                request_bits,
+                latencies_ns,
+            });
+        }
+    }
+    samples
+}
+
+pub fn write_csv(dir: &Path, samples: &[LatencySample]) -> Result<(), Box<dyn Error>> {
+    let mut file = File::create(dir.join("latency.csv"))?;
+    writeln!(
+        file,
+        "generator,cryptographic,request_bits,calls,mean_ns,median_ns,p90_ns,p99_ns,p999_ns,max_ns"
+    )?;
+    for s in samples {
+        let total: u64 = s.latencies_ns.iter().sum();
+        writeln!(
+            file,
+            "{},{},{},{},{:.1},{},{},{},{},{}",
+            s.generator,
+            s.cryptographic,
+            s.request_bits,
+            s.latencies_ns.len(),
+            total as f64 / s.latencies_ns.len() as f64,
+            quantile(&s.latencies_ns, 0.5),
+            quantile(&s.latencies_ns, 0.9),
+            quantile(&s.latencies_ns, 0.99),
+            quantile(&s.latencies_ns, 0.999),
+            s.latencies_ns.last().copied().unwrap_or_default()
+        )?;
+    }
+    Ok(())
+}
@@ -0,0 +1,10 @@
+//! DRBG implementations and the default benchmark parameters, shared by the
+//! benchmark binary and the Criterion benches.
+
+pub mod drbg;
+
+/// Target lengths in bits measured unless others are configured.
+pub const DEFAULT_LENGTHS: [usize; 4] = [10_000, 100_000, 1_000_000, 10_000_000];
+/// Base seed from which every measurement's seed is derived unless another is
+/// configured.
+pub const DEFAULT_SEED: &[u8] = b"cs-drbg-benchmark-seed-v1";
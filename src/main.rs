@@ -1,147 +1,1515 @@
-mod drbg;
+mod allocations;
+mod baseline;
+mod battery;
+mod checkpoint;
+mod cli;
+mod cold_start;
+mod config;
+mod cycles;
+mod entropy;
+mod host;
+mod latency;
+mod parquet;
+mod perf;
+mod rapl;
+mod rss;
+mod scaling;
+mod seed_sweep;
+mod sqlite;
+mod stats;
+mod workload;
 
-use crate::drbg::{AesCtrDrbg, Blake3XofDrbg, ChaCha20Drbg, Drbg};
+use clap::Parser;
+use cli::{Cli, Command, Mode};
+use config::{Config, Tests};
+use indicatif::{ProgressBar, ProgressStyle};
 use plotters::prelude::*;
-use std::collections::BTreeMap;
+use rand_core::{RngCore, SeedableRng};
+use rand_pcg::Pcg64;
+use random_numbers::drbg::{self, BitString, build_generators};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use std::error::Error;
 use std::fs::{self, File};
-use std::io::Write;
+use std::io::{BufWriter, IsTerminal, Write};
 use std::path::Path;
-use std::time::Instant;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+use tracing::{Level, debug, debug_span, info, info_span, warn};
+use tracing_subscriber::fmt::format::FmtSpan;
 
-const TARGET_LENGTHS: [usize; 4] = [10_000, 100_000, 1_000_000, 10_000_000];
-const RUNS: usize = 50;
-const BASE_SEED: &[u8] = b"cs-drbg-benchmark-seed-v1";
+#[global_allocator]
+static ALLOCATOR: allocations::CountingAllocator = allocations::CountingAllocator;
 
-#[derive(Clone)]
+// Default for `--regression-threshold`: slowdown in percent against the
+// `--baseline` that fails the run.
+const REGRESSION_THRESHOLD_PERCENT: f64 = 10.0;
+// Base seeds of `--mode seed-sweep` unless `--seeds` says otherwise.
+const SEED_SWEEP_SEEDS: usize = 8;
+// Smallest request that is also timed through `Drbg::generate_bits_parallel`.
+const PARALLEL_MIN_BITS: usize = 1_000_000;
+// Coverage of the confidence intervals in `summary.csv` and the plot bands.
+const CONFIDENCE_LEVEL: f64 = 0.95;
+// Seed of the shuffled measurement order, fixed so runs are repeatable.
+const ORDER_SEED: u64 = 81;
+// Request size of `generate`; large enough to amortise the per-request cost
+// of the SP 800-90A generators.
+const GENERATE_CHUNK_BYTES: usize = 1 << 20;
+// Block size M for the SP 800-22 block frequency test (M >= 20 recommended).
+const BLOCK_FREQUENCY_M: usize = 128;
+// Block length m for the approximate entropy test; m < log2(n) - 5 must hold
+// for the shortest target length.
+const APPROXIMATE_ENTROPY_M: usize = 7;
+// Bit shifts d at which the autocorrelation test runs.
+const AUTOCORRELATION_SHIFTS: [usize; 4] = [1, 2, 8, 16];
+// Output length compared in the seed avalanche experiment.
+const AVALANCHE_BITS: usize = 4_096;
+// Timed `new()` and `reseed()` calls per generator.
+const INSTANTIATION_RUNS: usize = 100;
+// Sequence length and largest lag for the per-generator lag-spectrum plots.
+const LAG_SPECTRUM_BITS: usize = 1_000_000;
+const LAG_SPECTRUM_MAX_LAG: usize = 256;
+
+#[derive(Clone, Serialize, Deserialize)]
 struct Record {
     run: usize,
     generator: String,
+    cryptographic: bool,
     bits: usize,
     duration_ms: f64,
+    throughput_mb_s: f64,
+    bits_per_ns: f64,
+    // None where `cycles::read` has no counter.
+    cycles: Option<u64>,
+    outlier: bool,
     storage_bytes: usize,
+    // Heap allocations made inside `generate_bits`.
+    allocations: u64,
+    allocated_bytes: u64,
+    // Process peak RSS over the generation; None off Linux.
+    peak_rss_bytes: Option<u64>,
+    // Only collected with `--perf-counters`.
+    perf: Option<perf::Counts>,
+    // Package energy over the generation; None without readable RAPL.
+    energy_j: Option<f64>,
     zeros: u64,
     ones: u64,
+    // None where switched off in `Config::tests`.
+    sts: Option<battery::StsResults>,
+    extended: Option<battery::ExtendedResults>,
+    min_entropy: Option<entropy::MinEntropyEstimates>,
+    ent: stats::EntResults,
+    compression_ratio: f64,
+    // `id` of the environment.json the record was measured in; empty in
+    // checkpoints from before the field existed.
+    #[serde(default)]
+    environment: String,
+    // `Config::tag` of the sweep.
+    #[serde(default)]
+    tag: Option<String>,
+}
+
+struct InstantiationCost {
+    generator: String,
+    cryptographic: bool,
+    new_us: Vec<f64>,
+    reseed_us: Vec<f64>,
+}
+
+struct AvalancheSample {
+    generator: String,
+    cryptographic: bool,
+    flipped_bit: usize,
+    distance: u64,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Serialize)]
 struct Summary {
     generator: String,
+    cryptographic: bool,
     bits: usize,
     runs: usize,
+    // Runs left out of the timing columns by `mark_outliers`.
+    outliers: usize,
     mean_time_ms: f64,
     std_time_ms: f64,
+    // Half-widths of the Student-t confidence intervals of the means.
+    time_ci_ms: f64,
+    ones_ratio_ci: f64,
+    // Distribution of all runs' times, outliers included.
+    median_time_ms: f64,
+    p5_time_ms: f64,
+    p95_time_ms: f64,
+    min_time_ms: f64,
+    max_time_ms: f64,
+    mean_throughput_mb_s: f64,
+    mean_bits_per_ns: f64,
+    // None when no cycle counter was available.
+    mean_cycles_per_byte: Option<f64>,
+    mean_energy_nj_per_byte: Option<f64>,
     mean_ones_ratio: f64,
     std_ones_ratio: f64,
+    // None where the tests were switched off.
+    mean_block_frequency_p: Option<f64>,
+    mean_runs_p: Option<f64>,
+    mean_approximate_entropy_p: Option<f64>,
+    mean_min_entropy: Option<f64>,
+    mean_shannon_entropy: f64,
+    mean_compression_ratio: f64,
+    // Lowest second-level p-values over all battery tests; None when both
+    // batteries were switched off.
+    min_uniformity_p: Option<f64>,
+    min_ks_p: Option<f64>,
     storage_bytes: usize,
+    // Highest peak RSS of any run.
+    peak_rss_bytes: Option<u64>,
+    tag: Option<String>,
 }
 
 fn main() -> Result<(), Box<dyn Error>> {
-    fs::create_dir_all("results/plots")?;
+    let cli = Cli::parse();
+    tracing_subscriber::fmt()
+        .with_max_level(match cli.verbose {
+            0 => Level::WARN,
+            1 => Level::INFO,
+            2 => Level::DEBUG,
+            _ => Level::TRACE,
+        })
+        // Closing spans log their duration.
+        .with_span_events(FmtSpan::CLOSE)
+        .with_writer(std::io::stderr)
+        .with_ansi(std::io::stderr().is_terminal())
+        .init();
+    match cli.command.unwrap_or(Command::Bench(cli.bench)) {
+        Command::Bench(args) => bench(&args),
+        Command::Test(args) => test(&args.config()?),
+        Command::Generate(args) => generate(&args),
+        Command::Plot(args) => {
+            let dir = args.config()?.output_dir;
+            fs::create_dir_all(dir.join("plots"))?;
+            plot_summaries(&dir, &read_summary_csv(&dir.join("summary.csv"))?)?;
+            println!(
+                "Redrew plots in {} from summary.csv",
+                dir.join("plots").display()
+            );
+            Ok(())
+        }
+        Command::Report(args) => {
+            let dir = args.config()?.output_dir;
+            report(&read_summary_csv(&dir.join("summary.csv"))?)
+        }
+        Command::ListGenerators => list_generators(),
+    }
+}
+
+fn bench(args: &cli::BenchArgs) -> Result<(), Box<dyn Error>> {
+    let config = args.experiment.config()?;
+    let dir = config.output_dir.as_path();
+    fs::create_dir_all(dir.join("plots/autocorrelation"))?;
+    match args.mode {
+        Mode::Sweep => {}
+        Mode::Scaling => {
+            let _span = info_span!("scaling").entered();
+            let samples = scaling::run(&config);
+            scaling::write_csv(dir, &samples)?;
+            scaling::plot(&samples, &dir.join("plots/scaling"))?;
+            println!(
+                "Wrote scaling.csv to {} and plots to plots/scaling",
+                dir.display()
+            );
+            return Ok(());
+        }
+        Mode::SeedSweep => {
+            let _span = info_span!("seed_sweep").entered();
+            seed_sweep::write_csv(dir, &seed_sweep::run(&config, args.seeds))?;
+            println!(
+                "Wrote seed_sweep.csv and seed_summary.csv to {}",
+                dir.display()
+            );
+            return Ok(());
+        }
+        Mode::Latency => {
+            let _span = info_span!("latency").entered();
+            latency::write_csv(dir, &latency::run(&config))?;
+            println!("Wrote latency.csv to {}", dir.display());
+            return Ok(());
+        }
+        Mode::ColdStart => {
+            let _span = info_span!("cold_start").entered();
+            cold_start::write_csv(dir, &cold_start::run(&config))?;
+            println!("Wrote cold_start.csv to {}", dir.display());
+            return Ok(());
+        }
+        Mode::Workload => {
+            let _span = info_span!("workload").entered();
+            workload::write_csv(dir, &workload::run(&config, args.workload_distribution()))?;
+            println!("Wrote workload.csv to {}", dir.display());
+            return Ok(());
+        }
+    }
+
+    if let Some(name) = &args.baseline {
+        baseline::check(name)?;
+    }
+    // Before anything in `dir` is rewritten, so a mismatched --resume leaves
+    // the interrupted sweep as it was.
+    let (mut checkpoint, done) = if args.resume {
+        checkpoint::Checkpoint::resume(dir, &config)?
+    } else {
+        (checkpoint::Checkpoint::create(dir, &config)?, Vec::new())
+    };
+
+    if let Some(core) = args.pin_core {
+        // Starts rayon's pool before pinning, so the parallel fills keep
+        // every core.
+        rayon::current_num_threads();
+        host::pin_to_core(core).map_err(|e| format!("cannot pin to core {core}: {e}"))?;
+    }
+    let started_at = SystemTime::now();
+    let frequency = host::frequency_state(args.pin_core.unwrap_or(0));
+    host::write_metadata(dir, &config.seed, args.pin_core, &frequency)?;
+    let environment = host::environment(args.pin_core, &frequency);
+    write_json(dir, "environment.json", &environment)?;
+
+    let mut instruments = Instruments {
+        perf_counters: None,
+        rapl: rapl::Rapl::open(),
+    };
+    if args.perf_counters {
+        match perf::Counters::new() {
+            Ok(counters) => instruments.perf_counters = Some(counters),
+            Err(e) => warn!("performance counters unavailable, continuing without: {e}"),
+        }
+    }
 
+    info_span!("warmup").in_scope(|| warm_up(&config));
+
+    // Every (run, length, generator) measurement in one shuffled sequence, so
+    // no generator consistently runs on a hot package or at a different turbo
+    // frequency than the others; the records are put back in nested-loop
+    // order afterwards.
+    let constructors = config.constructors();
+    info!(
+        generators = constructors.len(),
+        lengths = ?config.lengths,
+        runs = config.runs,
+        rapl = instruments.rapl.is_some(),
+        perf_counters = instruments.perf_counters.is_some(),
+        "starting sweep"
+    );
+    let mut order = Vec::new();
+    for run in 0..config.runs {
+        for &bits in &config.lengths {
+            for parallel in [false, true] {
+                if parallel && bits < PARALLEL_MIN_BITS {
+                    continue;
+                }
+                for index in 0..constructors.len() {
+                    order.push((run, bits, parallel, index));
+                }
+            }
+        }
+    }
+    if !args.no_shuffle {
+        shuffle(&mut order, ORDER_SEED);
+    }
+
+    // Cells the checkpoint already holds are taken over as they are; the
+    // others are measured in their place in the shuffled order.
     let mut records = Vec::new();
-    for run in 0..RUNS {
-        for &bits in TARGET_LENGTHS.iter() {
-            let seed = make_seed(run, bits);
-            let mut generators = build_generators(&seed);
-            for drbg in generators.iter_mut() {
-                let start = Instant::now();
-                let bitstring = drbg.generate_bits(bits);
-                let duration_ms = start.elapsed().as_secs_f64() * 1_000.0;
-                let tally = bitstring.count_bits();
-
-                records.push(Record {
+    if args.resume {
+        let mut done: HashMap<(String, usize, usize), Record> = done
+            .into_iter()
+            .map(|r| ((r.generator.clone(), r.bits, r.run), r))
+            .collect();
+        let names: Vec<String> = constructors
+            .iter()
+            .map(|new| new(&config.seed).expect("available").name().to_string())
+            .collect();
+        let total = order.len();
+        order.retain(|&(run, bits, parallel, index)| {
+            let name = if parallel {
+                format!("{} (parallel)", names[index])
+            } else {
+                names[index].clone()
+            };
+            match done.remove(&(name, bits, run)) {
+                Some(record) => {
+                    records.push(((run, bits, parallel, index), record));
+                    false
+                }
+                None => true,
+            }
+        });
+        eprintln!(
+            "Resuming: {} of {total} measurements already recorded",
+            records.len()
+        );
+        let elsewhere = records
+            .iter()
+            .filter(|(_, r)| r.environment != environment.id)
+            .count();
+        if elsewhere > 0 {
+            warn!(
+                "{elsewhere} resumed measurements were taken in another environment than {}",
+                environment.id
+            );
+        }
+    }
+    let cool_down = args.cool_down_ms.map(Duration::from_millis);
+
+    let progress = progress_bar(order.iter().map(|&(_, bits, _, _)| bits as u64).sum());
+    let sweep = info_span!("sweep", measurements = order.len()).entered();
+    for &(run, bits, parallel, index) in &order {
+        // Generators are rebuilt per measurement from the (run, length) seed;
+        // parallel twins therefore reproduce the sequential output.
+        let Some(mut drbg) = constructors[index](&config.seed_for(run, bits)) else {
+            progress.inc(bits as u64);
+            continue;
+        };
+        progress.set_message(format!("{} at {bits} bits", drbg.name()));
+        let _span = debug_span!("measure", generator = drbg.name(), bits, run, parallel).entered();
+        if let Some(pause) = cool_down {
+            thread::sleep(pause);
+        }
+        let cryptographic = drbg.is_cryptographic();
+        let record = if parallel {
+            let name = format!("{} (parallel)", drbg.name());
+            measure(
+                run,
+                name,
+                cryptographic,
+                bits,
+                &mut instruments,
+                config.tests,
+                || drbg.generate_bits_parallel(bits),
+            )
+        } else {
+            let name = drbg.name().to_string();
+            measure(
+                run,
+                name,
+                cryptographic,
+                bits,
+                &mut instruments,
+                config.tests,
+                || Some(drbg.generate_bits(bits)),
+            )
+        };
+        if let Some(mut record) = record {
+            record.environment = environment.id.clone();
+            checkpoint.append(&record)?;
+            records.push(((run, bits, parallel, index), record));
+        }
+        progress.inc(bits as u64);
+    }
+    progress.finish_and_clear();
+    sweep.exit();
+    let _analysis = info_span!("analysis").entered();
+    records.sort_by_key(|&(key, _)| key);
+    // Resumed records included: the tag labels the sweep, not the session.
+    let mut records: Vec<Record> = records
+        .into_iter()
+        .map(|(_, r)| Record {
+            tag: config.tag.clone(),
+            ..r
+        })
+        .collect();
+
+    mark_outliers(&mut records, config.outlier_mads);
+    write_csv(dir, &records)?;
+    write_json(dir, "metrics.json", &records)?;
+    if args.parquet {
+        parquet::write_records(dir, &records)?;
+        println!("Wrote metrics.parquet to {}", dir.display());
+    }
+    let verdicts = battery_verdicts(&records);
+    let summaries = summarize(&records, &verdicts);
+    write_summary_csv(dir, &summaries)?;
+    write_json(dir, "summary.json", &summaries)?;
+    sqlite::append(dir, &config, started_at, &environment, &records, &summaries)?;
+    write_battery_outputs(dir, &records, &verdicts)?;
+    write_comparisons_csv(dir, &records)?;
+    plot_summaries(dir, &summaries)?;
+
+    write_seed_analyses(&config)?;
+
+    let costs = info_span!("instantiation").in_scope(|| instantiation_costs(&config));
+    write_instantiation_csv(dir, &costs)?;
+    plot_instantiation(&costs, &dir.join("plots/instantiation.png"))?;
+
+    println!(
+        "Wrote metrics.csv, metrics.json, summary.csv, summary.json, results.sqlite, template_results.csv, random_excursions.csv, battery.csv, ent.csv, comparisons.csv, avalanche.csv, instantiation.csv, metadata.csv and environment.json to {} and plots to plots (lag spectra in plots/autocorrelation)",
+        dir.display()
+    );
+
+    let summary_path = dir.join("summary.csv");
+    if let Some(name) = &args.baseline {
+        let threshold = args.regression_threshold;
+        let deltas = baseline::compare(name, &summary_path)?;
+        baseline::write_csv(dir, &deltas, threshold)?;
+        let regressions: Vec<_> = deltas
+            .iter()
+            .filter(|d| d.delta_percent > threshold)
+            .collect();
+        for d in &regressions {
+            eprintln!(
+                "Regression: {} at {} bits is {:.1}% slower than baseline {name}",
+                d.generator, d.bits, d.delta_percent
+            );
+        }
+        if !regressions.is_empty() {
+            return Err(format!(
+                "{} configuration(s) regressed by more than {threshold}%",
+                regressions.len()
+            )
+            .into());
+        }
+        println!(
+            "Compared {} configurations against baseline {name}; see {}",
+            deltas.len(),
+            dir.join("baseline_comparison.csv").display()
+        );
+    }
+    if let Some(name) = &args.save_baseline {
+        let path = baseline::save(name, &summary_path)?;
+        println!("Saved baseline {name} to {}", path.display());
+    }
+    Ok(())
+}
+
+/// Pays for cold caches, page faults and lazy initialisation (e.g. the
+/// hardware-RNG feature probes) before anything is timed.
+fn warm_up(config: &Config) {
+    let warmup_seed = config.seed_for(config.runs, 0);
+    for &bits in &config.lengths {
+        for drbg in config.build_generators(&warmup_seed).iter_mut() {
+            for _ in 0..config.warmup_runs {
+                drbg.generate_bits(bits);
+                if bits >= PARALLEL_MIN_BITS {
+                    drbg.generate_bits_parallel(bits);
+                }
+            }
+        }
+    }
+}
+
+/// The statistical battery alone: every generator's output at every target
+/// length and run, without warm-up, parallel fills or the timing outputs.
+fn test(config: &Config) -> Result<(), Box<dyn Error>> {
+    let dir = config.output_dir.as_path();
+    fs::create_dir_all(dir.join("plots/autocorrelation"))?;
+    let progress = progress_bar(
+        (config.runs * config.lengths.iter().sum::<usize>() * config.constructors().len()) as u64,
+    );
+    let mut records = Vec::new();
+    for run in 0..config.runs {
+        let _run = info_span!("run", run).entered();
+        for &bits in &config.lengths {
+            for drbg in config
+                .build_generators(&config.seed_for(run, bits))
+                .iter_mut()
+            {
+                progress.set_message(format!("{} at {bits} bits", drbg.name()));
+                let _span = debug_span!("measure", generator = drbg.name(), bits).entered();
+                let name = drbg.name().to_string();
+                let cryptographic = drbg.is_cryptographic();
+                records.extend(measure(
                     run,
-                    generator: drbg.name().to_string(),
+                    name,
+                    cryptographic,
                     bits,
-                    duration_ms,
-                    storage_bytes: bitstring.storage_bytes(),
-                    zeros: tally.zeros,
-                    ones: tally.ones,
-                });
+                    &mut Instruments::default(),
+                    config.tests,
+                    || Some(drbg.generate_bits(bits)),
+                ));
+                progress.inc(bits as u64);
+            }
+        }
+    }
+    progress.finish_and_clear();
+    let _analysis = info_span!("analysis").entered();
+    write_battery_outputs(dir, &records, &battery_verdicts(&records))?;
+    write_seed_analyses(config)?;
+    println!(
+        "Wrote template_results.csv, random_excursions.csv, battery.csv, ent.csv and avalanche.csv to {} and plots to plots (lag spectra in plots/autocorrelation)",
+        dir.display()
+    );
+    Ok(())
+}
+
+/// Writes `args.bytes` bytes of one generator's output, or with `--stream`
+/// an endless stream, seeded like run 0 and produced `GENERATE_CHUNK_BYTES`
+/// at a time so the size is not bounded by memory.
+/// Every request size shares the same seed, so shorter outputs are prefixes
+/// of longer ones wherever the generator's output does not depend on how it
+/// is split into requests, as for the stream ciphers.
+fn generate(args: &cli::GenerateArgs) -> Result<(), Box<dyn Error>> {
+    let mut config = Config::default();
+    if let Some(seed) = args.seed.seed()? {
+        config.seed = seed;
+    }
+    let mut drbg = build_generators(&config.seed_for(0, 0))
+        .into_iter()
+        .find(|d| config::matches(&args.generator, d.name()))
+        .ok_or_else(|| format!("unknown generator {:?}", args.generator))?;
+    let mut out: Box<dyn Write> = match &args.out {
+        Some(path) => Box::new(BufWriter::new(File::create(path)?)),
+        None => Box::new(std::io::stdout().lock()),
+    };
+    let mut write = || -> std::io::Result<()> {
+        // None while streaming.
+        let mut remaining = args.bytes;
+        while remaining != Some(0) {
+            let chunk = remaining.map_or(GENERATE_CHUNK_BYTES, |r| r.min(GENERATE_CHUNK_BYTES));
+            out.write_all(&drbg.generate_bits(8 * chunk).bytes)?;
+            if let Some(r) = &mut remaining {
+                *r -= chunk;
             }
         }
+        out.flush()
+    };
+    match write() {
+        // The reader (`head -c`, a test suite) has seen enough.
+        Err(e) if e.kind() == std::io::ErrorKind::BrokenPipe => Ok(()),
+        result => Ok(result?),
     }
+}
 
-    write_csv(&records)?;
-    let summaries = summarize(&records);
-    write_summary_csv(&summaries)?;
+/// Table of every generator available here with its slug and parameters.
+fn list_generators() -> Result<(), Box<dyn Error>> {
+    let bits = |b: Option<usize>| b.map(|b| b.to_string()).unwrap_or_else(|| "-".to_string());
+    let mut out = std::io::stdout().lock();
+    writeln!(
+        out,
+        "| Generator | Slug | Cryptographic | Key bits | Block bits | Security strength |"
+    )?;
+    writeln!(out, "|---|---|---|---:|---:|---:|")?;
+    for drbg in Config::default().build_generators(&[]) {
+        writeln!(
+            out,
+            "| {} | {} | {} | {} | {} | {} |",
+            drbg.name(),
+            file_slug(drbg.name()),
+            if drbg.is_cryptographic() { "yes" } else { "no" },
+            bits(drbg.key_bits()),
+            bits(drbg.block_bits()),
+            bits(drbg.security_strength())
+        )?;
+    }
+    Ok(())
+}
+
+/// Per-length table of the main timing and quality figures, fastest first.
+fn report(summaries: &[Summary]) -> Result<(), Box<dyn Error>> {
+    let mut out = std::io::stdout().lock();
+    let mut by_length: BTreeMap<usize, Vec<&Summary>> = BTreeMap::new();
+    for s in summaries {
+        by_length.entry(s.bits).or_default().push(s);
+    }
+    for (bits, mut rows) in by_length {
+        rows.sort_by(|a, b| a.median_time_ms.total_cmp(&b.median_time_ms));
+        writeln!(out, "\n## {bits} bits\n")?;
+        writeln!(
+            out,
+            "| Generator | Cryptographic | Median time (ms) | MB/s | Cycles/byte | Ones ratio | Min uniformity p |"
+        )?;
+        writeln!(out, "|---|---|---:|---:|---:|---:|---:|")?;
+        for s in rows {
+            writeln!(
+                out,
+                "| {} | {} | {:.4} | {:.1} | {} | {:.5} | {} |",
+                s.generator,
+                if s.cryptographic { "yes" } else { "no" },
+                s.median_time_ms,
+                s.mean_throughput_mb_s,
+                s.mean_cycles_per_byte
+                    .map(|c| format!("{c:.2}"))
+                    .unwrap_or_else(|| "-".to_string()),
+                s.mean_ones_ratio,
+                s.min_uniformity_p
+                    .map(|p| format!("{p:.4}"))
+                    .unwrap_or_else(|| "-".to_string())
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Template, excursion, battery and ENT outputs of a set of records.
+fn write_battery_outputs(
+    dir: &Path,
+    records: &[Record],
+    verdicts: &BTreeMap<(String, usize), Vec<battery::Verdict>>,
+) -> Result<(), Box<dyn Error>> {
+    write_template_csv(dir, records)?;
+    write_excursions_csv(dir, records)?;
+    write_battery_csv(dir, verdicts)?;
+    write_ent_csv(dir, records)
+}
+
+/// Seed avalanche and lag spectra, which depend only on the base seed;
+/// each can be switched off in `config.tests`.
+fn write_seed_analyses(config: &Config) -> Result<(), Box<dyn Error>> {
+    let dir = config.output_dir.as_path();
+    let _span = info_span!("seed_analyses").entered();
+    if config.tests.avalanche {
+        let avalanche_samples = avalanche(config);
+        write_avalanche_csv(dir, &avalanche_samples)?;
+        plot_avalanche(&avalanche_samples, &dir.join("plots/avalanche.png"))?;
+    }
+
+    if config.tests.lag_spectra {
+        let seed = config.seed_for(0, LAG_SPECTRUM_BITS);
+        for drbg in config.build_generators(&seed).iter_mut() {
+            let bitstring = drbg.generate_bits(LAG_SPECTRUM_BITS);
+            let spectrum = stats::lag_spectrum(&bitstring, LAG_SPECTRUM_MAX_LAG);
+            let path = dir
+                .join("plots/autocorrelation")
+                .join(format!("{}.png", file_slug(drbg.name())));
+            plot_lag_spectrum(drbg.name(), &spectrum, &path)?;
+        }
+    }
+    Ok(())
+}
 
+fn plot_summaries(dir: &Path, summaries: &[Summary]) -> Result<(), Box<dyn Error>> {
     plot_summary_metric(
-        &summaries,
-        Path::new("results/plots/time_ms.png"),
+        summaries,
+        &dir.join("plots/time_ms.png"),
         "Generation time",
         "Time (ms)",
         |s| s.mean_time_ms,
+        Some(|s| s.time_ci_ms),
     )?;
     plot_summary_metric(
-        &summaries,
-        Path::new("results/plots/memory_bytes.png"),
+        summaries,
+        &dir.join("plots/throughput.png"),
+        "Throughput",
+        "MB/s",
+        |s| s.mean_throughput_mb_s,
+        None,
+    )?;
+    plot_cycles_per_byte(summaries, &dir.join("plots/cycles_per_byte.png"))?;
+    plot_summary_metric(
+        summaries,
+        &dir.join("plots/memory_bytes.png"),
         "Space consumption (packed bits)",
         "Bytes",
         |s| s.storage_bytes as f64,
+        None,
     )?;
     plot_summary_metric(
-        &summaries,
-        Path::new("results/plots/ones_ratio.png"),
+        summaries,
+        &dir.join("plots/ones_ratio.png"),
         "Proportion of ones",
         "Ones ratio",
         |s| s.mean_ones_ratio,
+        Some(|s| s.ones_ratio_ci),
+    )?;
+    // Empty, and so not drawn, when the STS battery was switched off.
+    let with_sts: Vec<Summary> = summaries
+        .iter()
+        .filter(|s| s.mean_runs_p.is_some())
+        .cloned()
+        .collect();
+    plot_summary_metric(
+        &with_sts,
+        &dir.join("plots/runs_p.png"),
+        "Runs test (mean p-value)",
+        "p-value",
+        |s| s.mean_runs_p.unwrap_or_default(),
+        None,
     )?;
+    plot_summary_metric(
+        &with_sts,
+        &dir.join("plots/approximate_entropy_p.png"),
+        "Approximate entropy test (mean p-value)",
+        "p-value",
+        |s| s.mean_approximate_entropy_p.unwrap_or_default(),
+        None,
+    )?;
+    plot_summary_metric(
+        summaries,
+        &dir.join("plots/shannon_entropy.png"),
+        "Shannon entropy of the byte histogram",
+        "Bits per byte",
+        |s| s.mean_shannon_entropy,
+        None,
+    )?;
+    Ok(())
+}
 
-    println!(
-        "Wrote results to results/metrics.csv, results/summary.csv and plots to results/plots"
+/// Progress over `total_bits` of generation on stderr, hidden when stderr is
+/// not a terminal or `--verbose` logs there. Bits rather than measurements
+/// are counted because time and test cost grow with the length, which keeps
+/// the ETA honest. Drawing happens only in `inc` and `set_message`, outside
+/// the timed sections.
+fn progress_bar(total_bits: u64) -> ProgressBar {
+    // Log lines would tear the bar apart.
+    if tracing::enabled!(Level::INFO) {
+        return ProgressBar::hidden();
+    }
+    let progress = ProgressBar::new(total_bits);
+    progress.set_style(
+        ProgressStyle::with_template("{elapsed_precise} [{bar:40}] {percent:>3}% ETA {eta} {msg}")
+            .expect("the progress template is valid")
+            .progress_chars("=> "),
     );
-    Ok(())
+    progress
 }
 
-fn write_csv(records: &[Record]) -> Result<(), Box<dyn Error>> {
-    let mut file = File::create("results/metrics.csv")?;
-    writeln!(
+/// Optional collectors read around every timed generation.
+#[derive(Default)]
+struct Instruments {
+    perf_counters: Option<perf::Counters>,
+    rapl: Option<rapl::Rapl>,
+}
+
+/// Fisher–Yates shuffle driven by a PCG seeded with `seed`.
+fn shuffle<T>(items: &mut [T], seed: u64) {
+    let mut rng = Pcg64::seed_from_u64(seed);
+    for i in (1..items.len()).rev() {
+        // The modulo bias is negligible against u64 for any realistic length.
+        let j = (rng.next_u64() % (i as u64 + 1)) as usize;
+        items.swap(i, j);
+    }
+}
+
+/// Times one call of `generate` and runs every test on its output; `None` if
+/// `generate` produced nothing.
+fn measure(
+    run: usize,
+    generator: String,
+    cryptographic: bool,
+    bits: usize,
+    instruments: &mut Instruments,
+    tests: Tests,
+    generate: impl FnOnce() -> Option<BitString>,
+) -> Option<Record> {
+    let rss_reset = rss::reset_peak();
+    let heap = allocations::snapshot();
+    let rapl = instruments.rapl.as_ref();
+    let start_energy = rapl.and_then(|r| r.read());
+    let perf_started = instruments
+        .perf_counters
+        .as_mut()
+        .is_some_and(|p| p.start().is_ok());
+    let start = Instant::now();
+    let start_cycles = cycles::read();
+    let bitstring = generate();
+    let end_cycles = cycles::read();
+    let duration_ms = start.elapsed().as_secs_f64() * 1_000.0;
+    let perf = instruments
+        .perf_counters
+        .as_mut()
+        .filter(|_| perf_started)
+        .and_then(|p| p.stop().ok());
+    let energy_j = rapl.and_then(|r| Some(r.joules_between(&start_energy?, &r.read()?)));
+    let heap = allocations::snapshot().since(heap);
+    let peak_rss_bytes = rss::peak_bytes().filter(|_| rss_reset);
+    let bitstring = bitstring?;
+    debug!(duration_ms, "generated");
+
+    let tally = bitstring.count_bits();
+    Some(Record {
+        run,
+        generator,
+        cryptographic,
+        bits,
+        duration_ms,
+        throughput_mb_s: throughput_mb_s(bits, duration_ms),
+        bits_per_ns: bits_per_ns(bits, duration_ms),
+        cycles: start_cycles.zip(end_cycles).map(|(s, e)| e.wrapping_sub(s)),
+        outlier: false,
+        storage_bytes: bitstring.storage_bytes(),
+        allocations: heap.allocations,
+        allocated_bytes: heap.bytes,
+        peak_rss_bytes,
+        perf,
+        energy_j,
+        zeros: tally.zeros,
+        ones: tally.ones,
+        sts: tests.sts.then(|| {
+            debug_span!("sts")
+                .in_scope(|| battery::run(&bitstring, BLOCK_FREQUENCY_M, APPROXIMATE_ENTROPY_M))
+        }),
+        extended: tests.extended.then(|| {
+            debug_span!("extended")
+                .in_scope(|| battery::run_extended(&bitstring, &AUTOCORRELATION_SHIFTS))
+        }),
+        min_entropy: tests
+            .min_entropy
+            .then(|| debug_span!("min_entropy").in_scope(|| entropy::estimate(&bitstring))),
+        ent: stats::ent(&bitstring),
+        compression_ratio: stats::compression_ratio(&bitstring),
+        environment: String::new(),
+        tag: None,
+    })
+}
+
+// Column groups of `metrics.csv` that stay empty when their tests are off.
+const STS_COLUMNS: &str = "monobit_p,block_frequency_p,runs,runs_p,longest_run_p,matrix_rank_p,spectral_p,template_uniformity_p,overlapping_template_p,maurer_p,mean_lfsr_length,linear_complexity_p,serial_p1,serial_p2,approximate_entropy_p,cusum_forward_p,cusum_backward_p,excursion_cycles";
+const EXTENDED_COLUMNS: &str = "byte_chi_square,byte_chi_square_p,poker_statistic,poker_p,poker_pass,birthday_samples,birthday_repeats,birthday_spacings_p,gap_p,overlapping_permutations_p";
+const MIN_ENTROPY_COLUMNS: &str = "min_entropy_mcv,min_entropy_collision,min_entropy_markov,min_entropy_compression,min_entropy_t_tuple,min_entropy_lrs,min_entropy";
+
+/// A run of empty fields standing in for a column group.
+fn empty_columns(columns: &str) -> String {
+    ",".repeat(columns.split(',').count())
+}
+
+fn write_csv(dir: &Path, records: &[Record]) -> Result<(), Box<dyn Error>> {
+    let mut file = File::create(dir.join("metrics.csv"))?;
+    write!(
         file,
-        "run,generator,bits,duration_ms,storage_bytes,zeros,ones,ones_ratio"
+        "run,generator,cryptographic,bits,duration_ms,throughput_mb_s,bits_per_ns,cycles,cycles_per_byte,outlier,storage_bytes,allocations,allocated_bytes,peak_rss_bytes,instructions,cache_misses,branch_misses,energy_j,energy_nj_per_byte,zeros,ones,ones_ratio,{STS_COLUMNS},{EXTENDED_COLUMNS},{MIN_ENTROPY_COLUMNS},serial_correlation,shannon_entropy,compression_ratio"
     )?;
+    for shift in AUTOCORRELATION_SHIFTS {
+        write!(
+            file,
+            ",autocorrelation_{shift}_z,autocorrelation_{shift}_p,autocorrelation_{shift}_pass"
+        )?;
+    }
+    writeln!(file, ",environment,tag")?;
     for r in records {
-        writeln!(
+        write!(
             file,
-            "{},{},{},{:.6},{},{},{},{:.6}",
+            "{},{},{},{},{:.6},{:.3},{:.6},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{:.6}",
             r.run,
             r.generator,
+            r.cryptographic,
             r.bits,
             r.duration_ms,
+            r.throughput_mb_s,
+            r.bits_per_ns,
+            r.cycles.map(|c| c.to_string()).unwrap_or_default(),
+            optional(cycles_per_byte(r)),
+            r.outlier,
             r.storage_bytes,
+            r.allocations,
+            r.allocated_bytes,
+            r.peak_rss_bytes.map(|b| b.to_string()).unwrap_or_default(),
+            r.perf
+                .map(|p| p.instructions.to_string())
+                .unwrap_or_default(),
+            r.perf
+                .map(|p| p.cache_misses.to_string())
+                .unwrap_or_default(),
+            r.perf
+                .map(|p| p.branch_misses.to_string())
+                .unwrap_or_default(),
+            optional(r.energy_j),
+            optional(energy_nj_per_byte(r)),
             r.zeros,
             r.ones,
             r.ones as f64 / r.bits as f64
         )?;
+        match &r.sts {
+            Some(sts) => write!(
+                file,
+                ",{:.6},{:.6},{},{:.6},{:.6},{:.6},{:.6},{:.6},{:.6},{},{:.3},{:.6},{:.6},{:.6},{:.6},{:.6},{:.6},{}",
+                sts.monobit_p,
+                sts.block_frequency_p,
+                sts.runs.runs,
+                sts.runs.p_value,
+                sts.longest_run_p,
+                sts.matrix_rank_p,
+                sts.spectral_p,
+                stats::uniformity(&sts.template_p),
+                sts.overlapping_template_p,
+                optional(sts.maurer_p),
+                sts.mean_lfsr_length,
+                sts.linear_complexity_p,
+                sts.serial.p_value_1,
+                sts.serial.p_value_2,
+                sts.approximate_entropy_p,
+                sts.cusum.forward_p,
+                sts.cusum.backward_p,
+                sts.excursions.cycles
+            )?,
+            None => write!(file, "{}", empty_columns(STS_COLUMNS))?,
+        }
+        match &r.extended {
+            Some(extended) => write!(
+                file,
+                ",{:.3},{:.6},{:.3},{:.6},{},{},{},{:.6},{},{}",
+                extended.byte_chi_square.statistic,
+                extended.byte_chi_square.p_value,
+                extended.poker.statistic,
+                extended.poker.p_value,
+                passes(extended.poker.p_value),
+                extended.birthday_spacings.samples,
+                extended.birthday_spacings.repeats,
+                extended.birthday_spacings.p_value,
+                optional(extended.gap.map(|g| g.p_value)),
+                optional(extended.overlapping_permutations.map(|o| o.p_value))
+            )?,
+            None => write!(file, "{}", empty_columns(EXTENDED_COLUMNS))?,
+        }
+        match &r.min_entropy {
+            Some(min_entropy) => write!(
+                file,
+                ",{:.6},{:.6},{:.6},{},{},{},{:.6}",
+                min_entropy.most_common_value,
+                min_entropy.collision,
+                min_entropy.markov,
+                optional(min_entropy.compression),
+                optional(min_entropy.t_tuple),
+                optional(min_entropy.lrs),
+                min_entropy.min()
+            )?,
+            None => write!(file, "{}", empty_columns(MIN_ENTROPY_COLUMNS))?,
+        }
+        write!(
+            file,
+            ",{:.6},{:.6},{:.6}",
+            r.ent.serial_correlation, r.ent.entropy, r.compression_ratio
+        )?;
+        match &r.extended {
+            Some(extended) => {
+                for a in &extended.autocorrelation {
+                    write!(file, ",{:.6},{:.6},{}", a.z, a.p_value, passes(a.p_value))?;
+                }
+            }
+            None => write!(file, "{}", ",,,".repeat(AUTOCORRELATION_SHIFTS.len()))?,
+        }
+        writeln!(
+            file,
+            ",{},{}",
+            r.environment,
+            r.tag.as_deref().unwrap_or_default()
+        )?;
     }
     Ok(())
 }
 
-fn write_summary_csv(summaries: &[Summary]) -> Result<(), Box<dyn Error>> {
-    let mut file = File::create("results/summary.csv")?;
+/// Per-run pass/fail flag at the significance level used throughout.
+fn passes(p_value: f64) -> bool {
+    p_value >= stats::SIGNIFICANCE_LEVEL
+}
+
+/// Formats a value that is not defined for every sequence; empty otherwise.
+fn optional(value: Option<f64>) -> String {
+    value.map(|v| format!("{v:.6}")).unwrap_or_default()
+}
+
+/// Writes `value` to `dir/name` as JSON; see the README for the schema.
+/// Non-finite floats, such as undefined p-values, become `null`.
+fn write_json<T: Serialize + ?Sized>(
+    dir: &Path,
+    name: &str,
+    value: &T,
+) -> Result<(), Box<dyn Error>> {
+    let mut file = BufWriter::new(File::create(dir.join(name))?);
+    serde_json::to_writer(&mut file, value)?;
+    file.flush()?;
+    Ok(())
+}
+
+fn write_summary_csv(dir: &Path, summaries: &[Summary]) -> Result<(), Box<dyn Error>> {
+    let mut file = File::create(dir.join("summary.csv"))?;
     writeln!(
         file,
-        "generator,bits,runs,mean_time_ms,std_time_ms,mean_ones_ratio,std_ones_ratio,storage_bytes"
+        "generator,cryptographic,bits,runs,outliers,mean_time_ms,std_time_ms,time_ci_low_ms,time_ci_high_ms,median_time_ms,p5_time_ms,p95_time_ms,min_time_ms,max_time_ms,mean_throughput_mb_s,mean_bits_per_ns,mean_cycles_per_byte,mean_energy_nj_per_byte,mean_ones_ratio,std_ones_ratio,ones_ratio_ci_low,ones_ratio_ci_high,mean_block_frequency_p,mean_runs_p,mean_approximate_entropy_p,mean_min_entropy,mean_shannon_entropy,mean_compression_ratio,min_uniformity_p,min_ks_p,storage_bytes,peak_rss_bytes,tag"
     )?;
     for s in summaries {
         writeln!(
             file,
-            "{},{},{},{:.6},{:.6},{:.6},{:.6},{}",
+            "{},{},{},{},{},{:.6},{:.6},{:.6},{:.6},{:.6},{:.6},{:.6},{:.6},{:.6},{:.3},{:.6},{},{},{:.6},{:.6},{:.6},{:.6},{},{},{},{},{:.6},{:.6},{},{},{},{},{}",
             s.generator,
+            s.cryptographic,
             s.bits,
             s.runs,
+            s.outliers,
             s.mean_time_ms,
             s.std_time_ms,
+            s.mean_time_ms - s.time_ci_ms,
+            s.mean_time_ms + s.time_ci_ms,
+            s.median_time_ms,
+            s.p5_time_ms,
+            s.p95_time_ms,
+            s.min_time_ms,
+            s.max_time_ms,
+            s.mean_throughput_mb_s,
+            s.mean_bits_per_ns,
+            optional(s.mean_cycles_per_byte),
+            optional(s.mean_energy_nj_per_byte),
             s.mean_ones_ratio,
             s.std_ones_ratio,
-            s.storage_bytes
+            s.mean_ones_ratio - s.ones_ratio_ci,
+            s.mean_ones_ratio + s.ones_ratio_ci,
+            optional(s.mean_block_frequency_p),
+            optional(s.mean_runs_p),
+            optional(s.mean_approximate_entropy_p),
+            optional(s.mean_min_entropy),
+            s.mean_shannon_entropy,
+            s.mean_compression_ratio,
+            optional(s.min_uniformity_p),
+            optional(s.min_ks_p),
+            s.storage_bytes,
+            s.peak_rss_bytes.map(|b| b.to_string()).unwrap_or_default(),
+            s.tag.as_deref().unwrap_or_default()
         )?;
     }
     Ok(())
 }
 
-fn summarize(records: &[Record]) -> Vec<Summary> {
+/// One data line of a CSV file, with fields looked up by header name.
+struct CsvRow<'a> {
+    header: &'a [&'a str],
+    fields: Vec<&'a str>,
+}
+
+impl CsvRow<'_> {
+    fn field(&self, name: &str) -> Result<&str, Box<dyn Error>> {
+        let index = self
+            .header
+            .iter()
+            .position(|&h| h == name)
+            .ok_or_else(|| format!("no {name} column"))?;
+        Ok(self.fields.get(index).copied().unwrap_or_default())
+    }
+
+    fn parse<T: std::str::FromStr>(&self, name: &str) -> Result<T, Box<dyn Error>>
+    where
+        T::Err: Error + 'static,
+    {
+        self.field(name)?
+            .parse()
+            .map_err(|e| format!("bad {name}: {e}").into())
+    }
+
+    /// `None` for an empty field, as written by [`optional`].
+    fn optional<T: std::str::FromStr>(&self, name: &str) -> Result<Option<T>, Box<dyn Error>>
+    where
+        T::Err: Error + 'static,
+    {
+        match self.field(name)? {
+            "" => Ok(None),
+            _ => self.parse(name).map(Some),
+        }
+    }
+}
+
+/// Reads back a `summary.csv` written by [`write_summary_csv`].
+fn read_summary_csv(path: &Path) -> Result<Vec<Summary>, Box<dyn Error>> {
+    let contents = fs::read_to_string(path).map_err(|e| {
+        format!(
+            "cannot read {}: {e}; run the benchmark first",
+            path.display()
+        )
+    })?;
+    let mut lines = contents.lines();
+    let header: Vec<&str> = lines.next().unwrap_or_default().split(',').collect();
+    let mut summaries = Vec::new();
+    for line in lines {
+        let row = CsvRow {
+            header: &header,
+            fields: line.split(',').collect(),
+        };
+        let read = || -> Result<Summary, Box<dyn Error>> {
+            let mean_time_ms = row.parse("mean_time_ms")?;
+            let mean_ones_ratio = row.parse("mean_ones_ratio")?;
+            Ok(Summary {
+                generator: row.field("generator")?.to_string(),
+                cryptographic: row.parse("cryptographic")?,
+                bits: row.parse("bits")?,
+                runs: row.parse("runs")?,
+                outliers: row.parse("outliers")?,
+                mean_time_ms,
+                std_time_ms: row.parse("std_time_ms")?,
+                time_ci_ms: row.parse::<f64>("time_ci_high_ms")? - mean_time_ms,
+                ones_ratio_ci: row.parse::<f64>("ones_ratio_ci_high")? - mean_ones_ratio,
+                median_time_ms: row.parse("median_time_ms")?,
+                p5_time_ms: row.parse("p5_time_ms")?,
+                p95_time_ms: row.parse("p95_time_ms")?,
+                min_time_ms: row.parse("min_time_ms")?,
+                max_time_ms: row.parse("max_time_ms")?,
+                mean_throughput_mb_s: row.parse("mean_throughput_mb_s")?,
+                mean_bits_per_ns: row.parse("mean_bits_per_ns")?,
+                mean_cycles_per_byte: row.optional("mean_cycles_per_byte")?,
+                mean_energy_nj_per_byte: row.optional("mean_energy_nj_per_byte")?,
+                mean_ones_ratio,
+                std_ones_ratio: row.parse("std_ones_ratio")?,
+                mean_block_frequency_p: row.optional("mean_block_frequency_p")?,
+                mean_runs_p: row.optional("mean_runs_p")?,
+                mean_approximate_entropy_p: row.optional("mean_approximate_entropy_p")?,
+                mean_min_entropy: row.optional("mean_min_entropy")?,
+                mean_shannon_entropy: row.parse("mean_shannon_entropy")?,
+                mean_compression_ratio: row.parse("mean_compression_ratio")?,
+                min_uniformity_p: row.optional("min_uniformity_p")?,
+                min_ks_p: row.optional("min_ks_p")?,
+                storage_bytes: row.parse("storage_bytes")?,
+                peak_rss_bytes: row.optional("peak_rss_bytes")?,
+                // Absent from files written before tags existed.
+                tag: row
+                    .field("tag")
+                    .ok()
+                    .filter(|tag| !tag.is_empty())
+                    .map(str::to_string),
+            })
+        };
+        summaries.push(read().map_err(|e| format!("{}: {e}", path.display()))?);
+    }
+    Ok(summaries)
+}
+
+/// Per-template results of the non-overlapping template test, aggregated over
+/// runs; header only when the STS battery was switched off.
+fn write_template_csv(dir: &Path, records: &[Record]) -> Result<(), Box<dyn Error>> {
+    let mut grouped: BTreeMap<(&str, usize), Vec<&battery::StsResults>> = BTreeMap::new();
+    for r in records {
+        if let Some(sts) = &r.sts {
+            grouped.entry((&r.generator, r.bits)).or_default().push(sts);
+        }
+    }
+
+    let templates = stats::aperiodic_templates(stats::TEMPLATE_LENGTH);
+    let mut file = File::create(dir.join("template_results.csv"))?;
+    writeln!(
+        file,
+        "generator,bits,template,runs,mean_p_value,pass_proportion,uniformity_p"
+    )?;
+    for ((generator, bits), samples) in grouped {
+        for (i, template) in templates.iter().enumerate() {
+            let p_values: Vec<f64> = samples.iter().map(|sts| sts.template_p[i]).collect();
+            let passed = p_values
+                .iter()
+                .filter(|&&p| p >= stats::SIGNIFICANCE_LEVEL)
+                .count();
+            writeln!(
+                file,
+                "{},{},{:0width$b},{},{:.6},{:.6},{:.6}",
+                generator,
+                bits,
+                template,
+                p_values.len(),
+                mean(p_values.iter().copied()),
+                passed as f64 / p_values.len() as f64,
+                stats::uniformity(&p_values),
+                width = stats::TEMPLATE_LENGTH
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Per-state p-values of both random excursions tests, aggregated over the runs
+/// with enough cycles for the tests to apply; configurations with none are omitted.
+fn write_excursions_csv(dir: &Path, records: &[Record]) -> Result<(), Box<dyn Error>> {
+    let mut grouped: BTreeMap<(&str, usize), Vec<&Record>> = BTreeMap::new();
+    for r in records {
+        grouped.entry((&r.generator, r.bits)).or_default().push(r);
+    }
+
+    let mut file = File::create(dir.join("random_excursions.csv"))?;
+    writeln!(
+        file,
+        "generator,bits,test,state,applicable_runs,mean_p_value,pass_proportion"
+    )?;
+    for ((generator, bits), samples) in grouped {
+        let excursions: Vec<&[f64; 8]> = samples
+            .iter()
+            .filter_map(|r| r.sts.as_ref()?.excursions.excursion_p.as_ref())
+            .collect();
+        let variants: Vec<&[f64; 18]> = samples
+            .iter()
+            .filter_map(|r| r.sts.as_ref()?.excursions.variant_p.as_ref())
+            .collect();
+        let tests = [
+            (
+                "random_excursions",
+                &stats::EXCURSION_STATES[..],
+                excursions.iter().map(|p| &p[..]).collect::<Vec<_>>(),
+            ),
+            (
+                "random_excursions_variant",
+                &stats::EXCURSION_VARIANT_STATES[..],
+                variants.iter().map(|p| &p[..]).collect::<Vec<_>>(),
+            ),
+        ];
+        for (test, states, runs) in tests {
+            if runs.is_empty() {
+                continue;
+            }
+            for (i, state) in states.iter().enumerate() {
+                let p_values: Vec<f64> = runs.iter().map(|p| p[i]).collect();
+                let passed = p_values
+                    .iter()
+                    .filter(|&&p| p >= stats::SIGNIFICANCE_LEVEL)
+                    .count();
+                writeln!(
+                    file,
+                    "{},{},{},{},{},{:.6},{:.6}",
+                    generator,
+                    bits,
+                    test,
+                    state,
+                    p_values.len(),
+                    mean(p_values.iter().copied()),
+                    passed as f64 / p_values.len() as f64
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// P-values of every battery test run on the record's sequence.
+fn p_values(r: &Record) -> battery::PValues {
+    let mut p_values = r.sts.as_ref().map(|sts| sts.p_values()).unwrap_or_default();
+    if let Some(extended) = &r.extended {
+        p_values.extend(extended.p_values());
+    }
+    p_values
+}
+
+/// SP 800-22 proportion and uniformity verdicts per test of both the STS and
+/// the extended battery, per generator and length across runs.
+fn battery_verdicts(records: &[Record]) -> BTreeMap<(String, usize), Vec<battery::Verdict>> {
+    let mut grouped: BTreeMap<(String, usize), Vec<battery::PValues>> = BTreeMap::new();
+    for r in records {
+        grouped
+            .entry((r.generator.clone(), r.bits))
+            .or_default()
+            .push(p_values(r));
+    }
+    grouped
+        .into_iter()
+        .map(|(key, samples)| (key, battery::evaluate(&samples)))
+        .collect()
+}
+
+fn write_battery_csv(
+    dir: &Path,
+    verdicts: &BTreeMap<(String, usize), Vec<battery::Verdict>>,
+) -> Result<(), Box<dyn Error>> {
+    let mut file = File::create(dir.join("battery.csv"))?;
+    writeln!(
+        file,
+        "generator,bits,test,sequences,passed,proportion,min_proportion,uniformity_p,ks_p,proportion_p,p_value,adjusted_p,result,adjusted_result"
+    )?;
+    for ((generator, bits), group) in verdicts {
+        for v in group {
+            writeln!(
+                file,
+                "{},{},{},{},{},{:.6},{:.6},{:.6},{:.6},{:.6},{:.6},{:.6},{},{}",
+                generator,
+                bits,
+                v.test,
+                v.sequences,
+                v.passed,
+                v.proportion,
+                v.min_proportion,
+                v.uniformity_p,
+                v.ks_p,
+                v.proportion_p,
+                v.p_value,
+                v.adjusted_p,
+                verdict_label(v.pass),
+                verdict_label(v.adjusted_pass)
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn verdict_label(pass: bool) -> &'static str {
+    if pass { "PASS" } else { "FAIL" }
+}
+
+/// The ENT metrics per run, in ENT's terms: the chi-square is reported with the
+/// percentage of times a random sequence would exceed it.
+fn write_ent_csv(dir: &Path, records: &[Record]) -> Result<(), Box<dyn Error>> {
+    let mut file = File::create(dir.join("ent.csv"))?;
+    writeln!(
+        file,
+        "run,generator,bits,entropy,chi_square,chi_square_exceed_percent,arithmetic_mean,monte_carlo_pi,pi_error_percent,serial_correlation"
+    )?;
+    for r in records {
+        let ent = &r.ent;
+        writeln!(
+            file,
+            "{},{},{},{:.6},{:.2},{:.2},{:.4},{:.9},{:.2},{:.6}",
+            r.run,
+            r.generator,
+            r.bits,
+            ent.entropy,
+            ent.chi_square.statistic,
+            100.0 * ent.chi_square.p_value,
+            ent.mean,
+            ent.monte_carlo_pi,
+            100.0 * (ent.monte_carlo_pi - std::f64::consts::PI).abs() / std::f64::consts::PI,
+            ent.serial_correlation
+        )?;
+    }
+    Ok(())
+}
+
+/// Welch's t-test and the Mann–Whitney U test on the non-outlier durations of
+/// every pair of generators at each target length.
+fn write_comparisons_csv(dir: &Path, records: &[Record]) -> Result<(), Box<dyn Error>> {
+    let mut grouped: BTreeMap<(usize, &str), Vec<f64>> = BTreeMap::new();
+    for r in records.iter().filter(|r| !r.outlier) {
+        grouped
+            .entry((r.bits, &r.generator))
+            .or_default()
+            .push(r.duration_ms);
+    }
+
+    let mut file = File::create(dir.join("comparisons.csv"))?;
+    writeln!(
+        file,
+        "bits,generator_a,generator_b,mean_time_a_ms,mean_time_b_ms,welch_t,welch_df,welch_p,cohens_d,mann_whitney_u,mann_whitney_p,rank_biserial"
+    )?;
+    let groups: Vec<_> = grouped
+        .iter()
+        .filter(|(_, times)| times.len() >= 2)
+        .collect();
+    for (i, ((bits, a), times_a)) in groups.iter().enumerate() {
+        for ((_, b), times_b) in groups[i + 1..]
+            .iter()
+            .filter(|((other, _), _)| other == bits)
+        {
+            let welch = stats::welch_t_test(times_a, times_b);
+            let mann_whitney = stats::mann_whitney_u(times_a, times_b);
+            writeln!(
+                file,
+                "{},{},{},{:.6},{:.6},{:.4},{:.2},{:.3e},{:.4},{:.1},{:.3e},{:.4}",
+                bits,
+                a,
+                b,
+                mean(times_a.iter().copied()),
+                mean(times_b.iter().copied()),
+                welch.t,
+                welch.df,
+                welch.p_value,
+                welch.cohens_d,
+                mann_whitney.u,
+                mann_whitney.p_value,
+                mann_whitney.rank_biserial
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Times each generator's constructor (seed derivation plus key schedule)
+/// and `reseed()` on run-specific seeds, `INSTANTIATION_RUNS` times each.
+fn instantiation_costs(config: &Config) -> Vec<InstantiationCost> {
+    let mut costs = Vec::new();
+    for new in config.constructors() {
+        let mut cost: Option<InstantiationCost> = None;
+        for run in 0..INSTANTIATION_RUNS {
+            let seed = config.seed_for(run, 0);
+            let start = Instant::now();
+            let Some(mut drbg) = new(&seed) else {
+                break;
+            };
+            let new_us = start.elapsed().as_secs_f64() * 1e6;
+
+            let reseed = config.seed_for(run + INSTANTIATION_RUNS, 0);
+            let start = Instant::now();
+            drbg.reseed(&reseed);
+            let reseed_us = start.elapsed().as_secs_f64() * 1e6;
+
+            let cost = cost.get_or_insert_with(|| InstantiationCost {
+                generator: drbg.name().to_string(),
+                cryptographic: drbg.is_cryptographic(),
+                new_us: Vec::new(),
+                reseed_us: Vec::new(),
+            });
+            cost.new_us.push(new_us);
+            cost.reseed_us.push(reseed_us);
+        }
+        costs.extend(cost);
+    }
+    costs
+}
+
+fn write_instantiation_csv(dir: &Path, costs: &[InstantiationCost]) -> Result<(), Box<dyn Error>> {
+    let mut file = File::create(dir.join("instantiation.csv"))?;
+    writeln!(
+        file,
+        "generator,cryptographic,runs,mean_new_us,median_new_us,std_new_us,mean_reseed_us,median_reseed_us,std_reseed_us"
+    )?;
+    for c in costs {
+        let mean_new = mean(c.new_us.iter().copied());
+        let mean_reseed = mean(c.reseed_us.iter().copied());
+        writeln!(
+            file,
+            "{},{},{},{:.3},{:.3},{:.3},{:.3},{:.3},{:.3}",
+            c.generator,
+            c.cryptographic,
+            c.new_us.len(),
+            mean_new,
+            median(&c.new_us),
+            stddev(c.new_us.iter().copied(), mean_new),
+            mean_reseed,
+            median(&c.reseed_us),
+            stddev(c.reseed_us.iter().copied(), mean_reseed)
+        )?;
+    }
+    Ok(())
+}
+
+/// Flips each bit of the run-0 seed in turn and records the Hamming distance
+/// between each generator's output and its output under the unmodified seed.
+fn avalanche(config: &Config) -> Vec<AvalancheSample> {
+    let seed = config.seed_for(0, AVALANCHE_BITS);
+    let baseline: Vec<BitString> = config
+        .build_generators(&seed)
+        .iter_mut()
+        .map(|drbg| drbg.generate_bits(AVALANCHE_BITS))
+        .collect();
+
+    let mut samples = Vec::new();
+    for flipped_bit in 0..seed.len() * 8 {
+        let mut flipped = seed.clone();
+        flipped[flipped_bit / 8] ^= 0x80 >> (flipped_bit % 8);
+        for (drbg, reference) in config.build_generators(&flipped).iter_mut().zip(&baseline) {
+            let output = drbg.generate_bits(AVALANCHE_BITS);
+            let distance = output
+                .bytes
+                .iter()
+                .zip(&reference.bytes)
+                .map(|(a, b)| (a ^ b).count_ones() as u64)
+                .sum();
+            samples.push(AvalancheSample {
+                generator: drbg.name().to_string(),
+                cryptographic: drbg.is_cryptographic(),
+                flipped_bit,
+                distance,
+            });
+        }
+    }
+    samples
+}
+
+fn write_avalanche_csv(dir: &Path, samples: &[AvalancheSample]) -> Result<(), Box<dyn Error>> {
+    let mut file = File::create(dir.join("avalanche.csv"))?;
+    writeln!(
+        file,
+        "generator,cryptographic,flipped_bit,hamming_distance,distance_ratio"
+    )?;
+    for s in samples {
+        writeln!(
+            file,
+            "{},{},{},{},{:.6}",
+            s.generator,
+            s.cryptographic,
+            s.flipped_bit,
+            s.distance,
+            s.distance as f64 / AVALANCHE_BITS as f64
+        )?;
+    }
+    Ok(())
+}
+
+fn summarize(
+    records: &[Record],
+    verdicts: &BTreeMap<(String, usize), Vec<battery::Verdict>>,
+) -> Vec<Summary> {
     let mut grouped: BTreeMap<(String, usize), Vec<&Record>> = BTreeMap::new();
     for r in records {
         grouped
@@ -153,34 +1521,137 @@ fn summarize(records: &[Record]) -> Vec<Summary> {
     let mut summaries = Vec::new();
     for ((generator, bits), samples) in grouped {
         let runs = samples.len();
-        let mean_time_ms = mean(samples.iter().map(|r| r.duration_ms));
-        let std_time_ms = stddev(samples.iter().map(|r| r.duration_ms), mean_time_ms);
+        let timed: Vec<&Record> = samples.iter().copied().filter(|r| !r.outlier).collect();
+        let mean_time_ms = mean(timed.iter().map(|r| r.duration_ms));
+        let std_time_ms = stddev(timed.iter().map(|r| r.duration_ms), mean_time_ms);
+        let times: Vec<f64> = samples.iter().map(|r| r.duration_ms).collect();
+        let mean_throughput_mb_s = mean(timed.iter().map(|r| r.throughput_mb_s));
+        let mean_bits_per_ns = mean(timed.iter().map(|r| r.bits_per_ns));
+        let cycles_per_byte: Vec<f64> = timed.iter().filter_map(|r| cycles_per_byte(r)).collect();
+        let mean_cycles_per_byte =
+            (!cycles_per_byte.is_empty()).then(|| mean(cycles_per_byte.iter().copied()));
+        let energy: Vec<f64> = timed.iter().filter_map(|r| energy_nj_per_byte(r)).collect();
+        let mean_energy_nj_per_byte = (!energy.is_empty()).then(|| mean(energy.iter().copied()));
         let ratios: Vec<f64> = samples
             .iter()
             .map(|r| r.ones as f64 / r.bits as f64)
             .collect();
         let mean_ones_ratio = mean(ratios.iter().copied());
         let std_ones_ratio = stddev(ratios.iter().copied(), mean_ones_ratio);
+        let sts: Vec<&battery::StsResults> =
+            samples.iter().filter_map(|r| r.sts.as_ref()).collect();
+        let mean_block_frequency_p = mean_if_any(sts.iter().map(|s| s.block_frequency_p));
+        let mean_runs_p = mean_if_any(sts.iter().map(|s| s.runs.p_value));
+        let mean_approximate_entropy_p = mean_if_any(sts.iter().map(|s| s.approximate_entropy_p));
+        let mean_min_entropy = mean_if_any(
+            samples
+                .iter()
+                .filter_map(|r| r.min_entropy.as_ref())
+                .map(|e| e.min()),
+        );
+        let mean_shannon_entropy = mean(samples.iter().map(|r| r.ent.entropy));
+        let mean_compression_ratio = mean(samples.iter().map(|r| r.compression_ratio));
+        let group = verdicts
+            .get(&(generator.clone(), bits))
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let min_uniformity_p = group.iter().map(|v| v.uniformity_p).reduce(f64::min);
+        let min_ks_p = group.iter().map(|v| v.ks_p).reduce(f64::min);
 
         summaries.push(Summary {
             generator,
+            cryptographic: samples[0].cryptographic,
             bits,
             runs,
+            outliers: runs - timed.len(),
             mean_time_ms,
             std_time_ms,
+            time_ci_ms: confidence_half_width(std_time_ms, timed.len()),
+            ones_ratio_ci: confidence_half_width(std_ones_ratio, runs),
+            median_time_ms: median(&times),
+            p5_time_ms: percentile(&times, 0.05),
+            p95_time_ms: percentile(&times, 0.95),
+            min_time_ms: times.iter().copied().fold(f64::INFINITY, f64::min),
+            max_time_ms: times.iter().copied().fold(0.0, f64::max),
+            mean_throughput_mb_s,
+            mean_bits_per_ns,
+            mean_cycles_per_byte,
+            mean_energy_nj_per_byte,
             mean_ones_ratio,
             std_ones_ratio,
+            mean_block_frequency_p,
+            mean_runs_p,
+            mean_approximate_entropy_p,
+            mean_min_entropy,
+            mean_shannon_entropy,
+            mean_compression_ratio,
+            min_uniformity_p,
+            min_ks_p,
             storage_bytes: samples[0].storage_bytes,
+            peak_rss_bytes: samples.iter().filter_map(|r| r.peak_rss_bytes).max(),
+            tag: samples[0].tag.clone(),
         });
     }
 
     summaries
 }
 
-fn mean<I: Iterator<Item = f64>>(mut iter: I) -> f64 {
+/// Flags the timings that lie more than `mads` scaled median absolute
+/// deviations from the median of their (generator, size) group. The MAD is
+/// scaled by 1.4826 so it estimates the standard deviation of normal data.
+/// Groups whose MAD is zero keep every run.
+fn mark_outliers(records: &mut [Record], mads: f64) {
+    let mut grouped: BTreeMap<(String, usize), Vec<usize>> = BTreeMap::new();
+    for (i, r) in records.iter().enumerate() {
+        grouped
+            .entry((r.generator.clone(), r.bits))
+            .or_default()
+            .push(i);
+    }
+
+    for indices in grouped.values() {
+        let times: Vec<f64> = indices.iter().map(|&i| records[i].duration_ms).collect();
+        let centre = median(&times);
+        let deviations: Vec<f64> = times.iter().map(|t| (t - centre).abs()).collect();
+        let mad = 1.4826 * median(&deviations);
+        if mad == 0.0 {
+            continue;
+        }
+        for (&i, deviation) in indices.iter().zip(deviations) {
+            records[i].outlier = deviation > mads * mad;
+        }
+    }
+}
+
+/// Output megabytes (10^6 bytes) per second.
+fn throughput_mb_s(bits: usize, duration_ms: f64) -> f64 {
+    (bits as f64 / 8.0 / 1e6) / (duration_ms / 1_000.0)
+}
+
+/// Counter ticks per output byte, the usual unit in cipher benchmarks.
+fn cycles_per_byte(r: &Record) -> Option<f64> {
+    r.cycles.map(|c| c as f64 / (r.bits as f64 / 8.0))
+}
+
+/// Package energy per output byte; includes whatever else ran on the package.
+fn energy_nj_per_byte(r: &Record) -> Option<f64> {
+    r.energy_j.map(|j| j * 1e9 / (r.bits as f64 / 8.0))
+}
+
+fn bits_per_ns(bits: usize, duration_ms: f64) -> f64 {
+    bits as f64 / (duration_ms * 1e6)
+}
+
+/// Mean of the values, or None when there are none.
+fn mean_if_any<I: Iterator<Item = f64>>(iter: I) -> Option<f64> {
+    let values: Vec<f64> = iter.collect();
+    (!values.is_empty()).then(|| mean(values.into_iter()))
+}
+
+fn mean<I: Iterator<Item = f64>>(iter: I) -> f64 {
     let mut count = 0f64;
     let mut sum = 0f64;
-    while let Some(v) = iter.next() {
+    for v in iter {
         sum += v;
         count += 1.0;
     }
@@ -202,28 +1673,314 @@ fn stddev<I: Iterator<Item = f64>>(iter: I, mean: f64) -> f64 {
     }
 }
 
-fn build_generators(seed: &[u8]) -> Vec<Box<dyn Drbg>> {
-    vec![
-        Box::new(ChaCha20Drbg::new(seed)),
-        Box::new(AesCtrDrbg::new(seed)),
-        Box::new(Blake3XofDrbg::new(seed)),
-    ]
+/// Half-width of the two-sided `CONFIDENCE_LEVEL` Student-t interval for the
+/// mean of `n` samples with sample standard deviation `std`.
+fn confidence_half_width(std: f64, n: usize) -> f64 {
+    if n < 2 {
+        return 0.0;
+    }
+    let t = stats::student_t_quantile(0.5 + CONFIDENCE_LEVEL / 2.0, (n - 1) as f64);
+    t * std / (n as f64).sqrt()
+}
+
+fn median(values: &[f64]) -> f64 {
+    percentile(values, 0.5)
+}
+
+/// The `q` quantile, interpolating linearly between order statistics.
+fn percentile(values: &[f64], q: f64) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let rank = q * (sorted.len() - 1) as f64;
+    let (low, high) = (rank.floor() as usize, rank.ceil() as usize);
+    sorted[low] + (rank - low as f64) * (sorted[high] - sorted[low])
+}
+
+/// Lowercase file name for a generator, with runs of other characters as `_`.
+fn file_slug(name: &str) -> String {
+    let mut slug = String::new();
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.ends_with('_') {
+            slug.push('_');
+        }
+    }
+    slug.trim_matches('_').to_string()
+}
+
+/// Bar chart of R(d) over the lags with the ±3/√n band expected for random
+/// bits; periodic structure shows up as spikes outside the band.
+fn plot_lag_spectrum(name: &str, spectrum: &[f64], path: &Path) -> Result<(), Box<dyn Error>> {
+    let band = 3.0 / (LAG_SPECTRUM_BITS as f64).sqrt();
+    let extent = spectrum.iter().fold(2.0 * band, |a, &r| a.max(r.abs())) * 1.1;
+
+    let root = BitMapBackend::new(path, (1200, 480)).into_drawing_area();
+    root.fill(&WHITE)?;
+    let mut chart = ChartBuilder::on(&root)
+        .caption(
+            format!("{name}: bit autocorrelation by lag"),
+            ("sans-serif", 26).into_font(),
+        )
+        .margin(20)
+        .x_label_area_size(50)
+        .y_label_area_size(90)
+        .build_cartesian_2d(0f64..(spectrum.len() as f64 + 1.0), -extent..extent)?;
+
+    chart
+        .configure_mesh()
+        .x_desc("Lag (bits)")
+        .x_label_formatter(&|x| format!("{x:.0}"))
+        .y_desc("R(d)")
+        .label_style(("sans-serif", 16))
+        .axis_desc_style(("sans-serif", 18))
+        .draw()?;
+
+    for level in [band, -band] {
+        chart.draw_series(LineSeries::new(
+            [(0.0, level), (spectrum.len() as f64 + 1.0, level)],
+            RED.stroke_width(1),
+        ))?;
+    }
+    chart.draw_series(spectrum.iter().enumerate().map(|(i, &r)| {
+        let lag = (i + 1) as f64;
+        Rectangle::new([(lag - 0.4, 0.0), (lag + 0.4, r)], BLUE.filled())
+    }))?;
+
+    root.present()?;
+    Ok(())
+}
+
+/// Strip chart of the avalanche distance ratios, one row per generator, with
+/// the mean marked in red; an ideal generator clusters tightly around 0.5.
+fn plot_avalanche(samples: &[AvalancheSample], path: &Path) -> Result<(), Box<dyn Error>> {
+    if samples.is_empty() {
+        return Ok(());
+    }
+
+    let mut grouped: BTreeMap<&str, Vec<f64>> = BTreeMap::new();
+    for s in samples {
+        grouped
+            .entry(&s.generator)
+            .or_default()
+            .push(s.distance as f64 / AVALANCHE_BITS as f64);
+    }
+    let names: Vec<&str> = grouped.keys().copied().collect();
+    let ratios = grouped.values().flatten();
+    let x_min = ratios.clone().fold(0.5f64, |a, &b| a.min(b)) - 0.01;
+    let x_max = ratios.fold(0.5f64, |a, &b| a.max(b)) + 0.01;
+
+    // Rows rather than columns keep the generator names readable.
+    let height = 120 + 22 * names.len() as u32;
+    let root = BitMapBackend::new(path, (1200, height)).into_drawing_area();
+    root.fill(&WHITE)?;
+    let mut chart = ChartBuilder::on(&root)
+        .caption(
+            "Seed avalanche (output bits changed per flipped seed bit)",
+            ("sans-serif", 26).into_font(),
+        )
+        .margin(20)
+        .x_label_area_size(50)
+        .y_label_area_size(220)
+        .build_cartesian_2d(x_min..x_max, (0..names.len()).into_segmented())?;
+
+    chart
+        .configure_mesh()
+        .y_labels(names.len() + 1)
+        .y_label_formatter(&|y| match y {
+            SegmentValue::CenterOf(i) => names.get(*i).copied().unwrap_or("").to_string(),
+            _ => String::new(),
+        })
+        .x_desc("Hamming distance / output bits")
+        .label_style(("sans-serif", 16))
+        .axis_desc_style(("sans-serif", 18))
+        .draw()?;
+
+    for (idx, values) in grouped.values().enumerate() {
+        chart.draw_series(values.iter().map(|&v| {
+            Circle::new(
+                (v, SegmentValue::CenterOf(idx)),
+                2,
+                Palette99::pick(idx).mix(0.4).filled(),
+            )
+        }))?;
+        let mean_ratio = mean(values.iter().copied());
+        chart.draw_series(std::iter::once(Cross::new(
+            (mean_ratio, SegmentValue::CenterOf(idx)),
+            6,
+            RED.stroke_width(2),
+        )))?;
+    }
+
+    root.present()?;
+    Ok(())
+}
+
+/// Horizontal bar chart of the median `new()` and `reseed()` cost per
+/// generator, on a log axis since they span several orders of magnitude.
+fn plot_instantiation(costs: &[InstantiationCost], path: &Path) -> Result<(), Box<dyn Error>> {
+    if costs.is_empty() {
+        return Ok(());
+    }
+
+    let medians: Vec<(f64, f64)> = costs
+        .iter()
+        .map(|c| (median(&c.new_us), median(&c.reseed_us)))
+        .collect();
+    let smallest = medians.iter().fold(f64::MAX, |a, &(n, r)| a.min(n).min(r));
+    let largest = medians.iter().fold(0.0f64, |a, &(n, r)| a.max(n).max(r));
+    let x_min = smallest.max(1e-3) / 2.0;
+    let x_max = largest.max(x_min * 10.0) * 2.0;
+
+    let height = 120 + 26 * costs.len() as u32;
+    let root = BitMapBackend::new(path, (1200, height)).into_drawing_area();
+    root.fill(&WHITE)?;
+    let mut chart = ChartBuilder::on(&root)
+        .caption(
+            "Instantiation and reseed cost (median)",
+            ("sans-serif", 26).into_font(),
+        )
+        .margin(20)
+        .x_label_area_size(50)
+        .y_label_area_size(220)
+        .build_cartesian_2d(
+            (x_min..x_max).log_scale(),
+            (0..costs.len()).into_segmented(),
+        )?;
+
+    chart
+        .configure_mesh()
+        .y_labels(costs.len() + 1)
+        .y_label_formatter(&|y| match y {
+            SegmentValue::CenterOf(i) => costs
+                .get(*i)
+                .map_or("", |c| c.generator.as_str())
+                .to_string(),
+            _ => String::new(),
+        })
+        .x_desc("Time (µs)")
+        .label_style(("sans-serif", 16))
+        .axis_desc_style(("sans-serif", 18))
+        .draw()?;
+
+    // new() in the lower half of each row, reseed() in the upper half.
+    chart
+        .draw_series(medians.iter().enumerate().map(|(i, &(new_us, _))| {
+            Rectangle::new(
+                [
+                    (x_min, SegmentValue::Exact(i)),
+                    (new_us, SegmentValue::CenterOf(i)),
+                ],
+                BLUE.mix(0.7).filled(),
+            )
+        }))?
+        .label("new()")
+        .legend(|(x, y)| Rectangle::new([(x, y - 5), (x + 20, y + 5)], BLUE.mix(0.7).filled()));
+    chart
+        .draw_series(medians.iter().enumerate().map(|(i, &(_, reseed_us))| {
+            Rectangle::new(
+                [
+                    (x_min, SegmentValue::CenterOf(i)),
+                    (reseed_us, SegmentValue::Exact(i + 1)),
+                ],
+                RED.mix(0.7).filled(),
+            )
+        }))?
+        .label("reseed()")
+        .legend(|(x, y)| Rectangle::new([(x, y - 5), (x + 20, y + 5)], RED.mix(0.7).filled()));
+
+    chart
+        .configure_series_labels()
+        .position(SeriesLabelPosition::UpperRight)
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .label_font(("sans-serif", 16))
+        .draw()?;
+    root.present()?;
+    Ok(())
 }
 
-fn make_seed(run: usize, bits: usize) -> Vec<u8> {
-    let mut seed = Vec::with_capacity(BASE_SEED.len() + 16);
-    seed.extend_from_slice(BASE_SEED);
-    seed.extend_from_slice(&(run as u64).to_be_bytes());
-    seed.extend_from_slice(&(bits as u64).to_be_bytes());
-    seed
+/// Horizontal bars of mean cycles per byte at the largest target length,
+/// cheapest first, on a log scale; skipped without a cycle counter.
+fn plot_cycles_per_byte(summaries: &[Summary], path: &Path) -> Result<(), Box<dyn Error>> {
+    let Some(bits) = summaries.iter().map(|s| s.bits).max() else {
+        return Ok(());
+    };
+    let mut bars: Vec<(&Summary, f64)> = summaries
+        .iter()
+        .filter(|s| s.bits == bits)
+        .filter_map(|s| Some((s, s.mean_cycles_per_byte?)))
+        .collect();
+    if bars.is_empty() {
+        return Ok(());
+    }
+    bars.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    let smallest = bars.iter().map(|&(_, c)| c).fold(f64::MAX, f64::min);
+    let largest = bars.iter().map(|&(_, c)| c).fold(0.0, f64::max);
+    let x_min = smallest.max(1e-3) / 2.0;
+    let x_max = largest.max(x_min * 10.0) * 2.0;
+
+    let height = 120 + 26 * bars.len() as u32;
+    let root = BitMapBackend::new(path, (1200, height)).into_drawing_area();
+    root.fill(&WHITE)?;
+    let mut chart = ChartBuilder::on(&root)
+        .caption(
+            format!("Cycles per byte at {bits} bits"),
+            ("sans-serif", 26).into_font(),
+        )
+        .margin(20)
+        .x_label_area_size(50)
+        .y_label_area_size(260)
+        .build_cartesian_2d((x_min..x_max).log_scale(), (0..bars.len()).into_segmented())?;
+
+    chart
+        .configure_mesh()
+        .y_labels(bars.len() + 1)
+        .y_label_formatter(&|y| match y {
+            SegmentValue::CenterOf(i) => bars
+                .get(*i)
+                .map_or("", |(s, _)| s.generator.as_str())
+                .to_string(),
+            _ => String::new(),
+        })
+        .x_desc("Cycle-counter ticks per output byte")
+        .label_style(("sans-serif", 16))
+        .axis_desc_style(("sans-serif", 18))
+        .draw()?;
+
+    // Non-cryptographic generators in grey, so the comparison that matters
+    // stands out.
+    chart.draw_series(bars.iter().enumerate().map(|(i, &(s, cycles))| {
+        let color = if s.cryptographic {
+            BLUE.mix(0.7)
+        } else {
+            BLACK.mix(0.3)
+        };
+        Rectangle::new(
+            [
+                (x_min, SegmentValue::Exact(i)),
+                (cycles, SegmentValue::Exact(i + 1)),
+            ],
+            color.filled(),
+        )
+    }))?;
+    root.present()?;
+    Ok(())
 }
 
+/// Line plot of one summary value over the target lengths, per generator;
+/// `band` gives a half-width drawn as a shaded band around each line.
 fn plot_summary_metric<F>(
     summaries: &[Summary],
     path: &Path,
     title: &str,
     y_label: &str,
     value: F,
+    band: Option<fn(&Summary) -> f64>,
 ) -> Result<(), Box<dyn Error>>
 where
     F: Fn(&Summary) -> f64,
@@ -234,8 +1991,15 @@ where
 
     let x_min = summaries.iter().map(|r| r.bits as u64).min().unwrap();
     let x_max = summaries.iter().map(|r| r.bits as u64).max().unwrap();
-    let mut y_min = summaries.iter().map(|r| value(r)).fold(f64::MAX, f64::min);
-    let mut y_max = summaries.iter().map(|r| value(r)).fold(f64::MIN, f64::max);
+    let half_width = |s: &Summary| band.map_or(0.0, |band| band(s));
+    let mut y_min = summaries
+        .iter()
+        .map(|s| value(s) - half_width(s))
+        .fold(f64::MAX, f64::min);
+    let mut y_max = summaries
+        .iter()
+        .map(|s| value(s) + half_width(s))
+        .fold(f64::MIN, f64::max);
     if y_min == y_max {
         y_min = 0.0;
         y_max *= 1.1;
@@ -275,12 +2039,30 @@ where
     for (idx, (name, series)) in grouped.iter().enumerate() {
         let color = Palette99::pick(idx);
         let legend_color = color.to_rgba();
+        let label = if series[0].cryptographic {
+            name.to_string()
+        } else {
+            format!("{name} (non-crypto)")
+        };
+        if band.is_some() {
+            let upper = series
+                .iter()
+                .map(|r| (r.bits as u64, value(r) + half_width(r)));
+            let lower = series
+                .iter()
+                .rev()
+                .map(|r| (r.bits as u64, value(r) - half_width(r)));
+            chart.draw_series(std::iter::once(Polygon::new(
+                upper.chain(lower).collect::<Vec<_>>(),
+                color.mix(0.2).filled(),
+            )))?;
+        }
         chart
             .draw_series(LineSeries::new(
                 series.iter().map(|r| (r.bits as u64, value(r))),
                 color.stroke_width(3),
             ))?
-            .label(*name)
+            .label(label)
             .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 25, y)], legend_color));
 
         chart.draw_series(
@@ -292,8 +2074,8 @@ where
 
     chart
         .configure_series_labels()
-        .background_style(&WHITE.mix(0.8))
-        .border_style(&BLACK)
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
         .label_font(("sans-serif", 16))
         .draw()?;
 
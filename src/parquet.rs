@@ -0,0 +1,146 @@
+//! Parquet copy of the per-run records (`bench --parquet`): one row per
+//! record, with the scalar `metrics.csv` columns followed by one p-value
+//! column per battery test, named `p_<test>` after the tests of
+//! `battery.csv`. Loads with `pd.read_parquet` or `pl.read_parquet` without
+//! parsing, which matters once the full battery makes the CSV unwieldy.
+
+use crate::{Record, cycles_per_byte, energy_nj_per_byte};
+use ::parquet::arrow::ArrowWriter;
+use ::parquet::basic::{Compression, ZstdLevel};
+use ::parquet::file::properties::WriterProperties;
+use arrow_array::{ArrayRef, BooleanArray, Float64Array, RecordBatch, StringArray, UInt64Array};
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+
+fn u64s(records: &[Record], f: impl Fn(&Record) -> Option<u64>) -> ArrayRef {
+    Arc::new(records.iter().map(f).collect::<UInt64Array>())
+}
+
+fn f64s(records: &[Record], f: impl Fn(&Record) -> Option<f64>) -> ArrayRef {
+    Arc::new(records.iter().map(f).collect::<Float64Array>())
+}
+
+fn bools(records: &[Record], f: impl Fn(&Record) -> bool) -> ArrayRef {
+    Arc::new(records.iter().map(|r| Some(f(r))).collect::<BooleanArray>())
+}
+
+/// Writes `metrics.parquet`, zstd-compressed. Tests that did not run on a
+/// record (switched off, or not applicable at its length) are null.
+pub fn write_records(dir: &Path, records: &[Record]) -> Result<(), Box<dyn Error>> {
+    let mut columns: Vec<(String, ArrayRef)> = vec![
+        ("run".into(), u64s(records, |r| Some(r.run as u64))),
+        (
+            "generator".into(),
+            Arc::new(StringArray::from_iter_values(
+                records.iter().map(|r| &r.generator),
+            )),
+        ),
+        ("cryptographic".into(), bools(records, |r| r.cryptographic)),
+        ("bits".into(), u64s(records, |r| Some(r.bits as u64))),
+        ("duration_ms".into(), f64s(records, |r| Some(r.duration_ms))),
+        (
+            "throughput_mb_s".into(),
+            f64s(records, |r| Some(r.throughput_mb_s)),
+        ),
+        ("bits_per_ns".into(), f64s(records, |r| Some(r.bits_per_ns))),
+        ("cycles".into(), u64s(records, |r| r.cycles)),
+        ("cycles_per_byte".into(), f64s(records, cycles_per_byte)),
+        ("outlier".into(), bools(records, |r| r.outlier)),
+        (
+            "storage_bytes".into(),
+            u64s(records, |r| Some(r.storage_bytes as u64)),
+        ),
+        ("allocations".into(), u64s(records, |r| Some(r.allocations))),
+        (
+            "allocated_bytes".into(),
+            u64s(records, |r| Some(r.allocated_bytes)),
+        ),
+        ("peak_rss_bytes".into(), u64s(records, |r| r.peak_rss_bytes)),
+        (
+            "instructions".into(),
+            u64s(records, |r| r.perf.map(|p| p.instructions)),
+        ),
+        (
+            "cache_misses".into(),
+            u64s(records, |r| r.perf.map(|p| p.cache_misses)),
+        ),
+        (
+            "branch_misses".into(),
+            u64s(records, |r| r.perf.map(|p| p.branch_misses)),
+        ),
+        ("energy_j".into(), f64s(records, |r| r.energy_j)),
+        (
+            "energy_nj_per_byte".into(),
+            f64s(records, energy_nj_per_byte),
+        ),
+        ("zeros".into(), u64s(records, |r| Some(r.zeros))),
+        ("ones".into(), u64s(records, |r| Some(r.ones))),
+        (
+            "ones_ratio".into(),
+            f64s(records, |r| Some(r.ones as f64 / r.bits as f64)),
+        ),
+        (
+            "min_entropy".into(),
+            f64s(records, |r| r.min_entropy.map(|e| e.min())),
+        ),
+        (
+            "serial_correlation".into(),
+            f64s(records, |r| Some(r.ent.serial_correlation)),
+        ),
+        (
+            "shannon_entropy".into(),
+            f64s(records, |r| Some(r.ent.entropy)),
+        ),
+        (
+            "compression_ratio".into(),
+            f64s(records, |r| Some(r.compression_ratio)),
+        ),
+        (
+            "environment".into(),
+            Arc::new(StringArray::from_iter_values(
+                records.iter().map(|r| &r.environment),
+            )),
+        ),
+        (
+            "tag".into(),
+            Arc::new(StringArray::from_iter(
+                records.iter().map(|r| r.tag.as_deref()),
+            )),
+        ),
+    ];
+
+    // Tests in order of first appearance, so the STS columns come first and
+    // the template columns keep their `template_results.csv` order.
+    let mut tests: Vec<String> = Vec::new();
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut p_values: Vec<HashMap<String, Option<f64>>> = Vec::with_capacity(records.len());
+    for r in records {
+        let record_p_values = crate::p_values(r);
+        for (test, _) in &record_p_values {
+            if seen.insert(test.clone()) {
+                tests.push(test.clone());
+            }
+        }
+        p_values.push(record_p_values.into_iter().collect());
+    }
+    for test in tests {
+        let column: Float64Array = p_values
+            .iter()
+            .map(|p| p.get(&test).copied().flatten().filter(|p| !p.is_nan()))
+            .collect();
+        columns.push((format!("p_{test}"), Arc::new(column)));
+    }
+
+    let batch = RecordBatch::try_from_iter(columns)?;
+    let properties = WriterProperties::builder()
+        .set_compression(Compression::ZSTD(ZstdLevel::default()))
+        .build();
+    let file = File::create(dir.join("metrics.parquet"))?;
+    let mut writer = ArrowWriter::try_new(file, batch.schema(), Some(properties))?;
+    writer.write(&batch)?;
+    writer.close()?;
+    Ok(())
+}
@@ -0,0 +1,82 @@
+//! Hardware performance counters through `perf_event_open`, counting user
+//! space only so it works at the default `perf_event_paranoid` level of 2.
+//! Linux only; elsewhere, and on machines without a PMU (most VMs),
+//! [`Counters::new`] fails.
+
+use serde::{Deserialize, Serialize};
+use std::io;
+
+/// Counter values for one measured stretch of code.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Counts {
+    pub instructions: u64,
+    pub cache_misses: u64,
+    pub branch_misses: u64,
+}
+
+#[cfg(target_os = "linux")]
+pub struct Counters {
+    group: perf_event::Group,
+    instructions: perf_event::Counter,
+    cache_misses: perf_event::Counter,
+    branch_misses: perf_event::Counter,
+}
+
+#[cfg(target_os = "linux")]
+impl Counters {
+    /// Opens the three counters as one group, so they are scheduled together.
+    pub fn new() -> io::Result<Counters> {
+        use perf_event::Builder;
+        use perf_event::events::Hardware;
+
+        let mut group = perf_event::Group::new()?;
+        let mut counter = |kind| Builder::new().group(&mut group).kind(kind).build();
+        let instructions = counter(Hardware::INSTRUCTIONS)?;
+        let cache_misses = counter(Hardware::CACHE_MISSES)?;
+        let branch_misses = counter(Hardware::BRANCH_MISSES)?;
+        Ok(Counters {
+            group,
+            instructions,
+            cache_misses,
+            branch_misses,
+        })
+    }
+
+    /// Zeroes and starts the counters.
+    pub fn start(&mut self) -> io::Result<()> {
+        self.group.reset()?;
+        self.group.enable()
+    }
+
+    /// Stops the counters and reads what they counted since [`Counters::start`].
+    pub fn stop(&mut self) -> io::Result<Counts> {
+        self.group.disable()?;
+        let counts = self.group.read()?;
+        Ok(Counts {
+            instructions: counts[&self.instructions],
+            cache_misses: counts[&self.cache_misses],
+            branch_misses: counts[&self.branch_misses],
+        })
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub struct Counters;
+
+#[cfg(not(target_os = "linux"))]
+impl Counters {
+    pub fn new() -> io::Result<Counters> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "perf_event_open is Linux only",
+        ))
+    }
+
+    pub fn start(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    pub fn stop(&mut self) -> io::Result<Counts> {
+        Err(io::ErrorKind::Unsupported.into())
+    }
+}
@@ -0,0 +1,81 @@
+//! Package energy from the RAPL counters in the Linux powercap interface
+//! (`/sys/class/powercap/intel-rapl:N`, also used for AMD). The counters
+//! cover the whole package, refresh about once per millisecond and are
+//! usually readable only by root; short generations therefore read as zero
+//! or a single refresh step.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const POWERCAP_DIR: &str = "/sys/class/powercap";
+
+struct Domain {
+    energy_path: PathBuf,
+    /// Counter value at which `energy_uj` wraps to zero.
+    max_energy_uj: u64,
+}
+
+/// The package-level RAPL domains of every socket.
+pub struct Rapl {
+    domains: Vec<Domain>,
+}
+
+/// Raw counter values of every domain, in microjoules.
+pub struct Reading(Vec<u64>);
+
+fn read_u64(path: &Path) -> Option<u64> {
+    fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+impl Rapl {
+    /// `None` without readable package domains.
+    pub fn open() -> Option<Rapl> {
+        let mut domains = Vec::new();
+        for entry in fs::read_dir(POWERCAP_DIR).ok()?.flatten() {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            // Packages are intel-rapl:N; intel-rapl:N:M are their sub-domains.
+            let Some(index) = name.strip_prefix("intel-rapl:") else {
+                continue;
+            };
+            if index.contains(':') {
+                continue;
+            }
+            let dir = entry.path();
+            let energy_path = dir.join("energy_uj");
+            if read_u64(&energy_path).is_none() {
+                continue;
+            }
+            domains.push(Domain {
+                energy_path,
+                max_energy_uj: read_u64(&dir.join("max_energy_range_uj")).unwrap_or(u64::MAX),
+            });
+        }
+        (!domains.is_empty()).then_some(Rapl { domains })
+    }
+
+    pub fn read(&self) -> Option<Reading> {
+        self.domains
+            .iter()
+            .map(|d| read_u64(&d.energy_path))
+            .collect::<Option<Vec<u64>>>()
+            .map(Reading)
+    }
+
+    /// Joules consumed by all packages between two readings, allowing for
+    /// one wrap-around of each counter.
+    pub fn joules_between(&self, start: &Reading, end: &Reading) -> f64 {
+        let microjoules: u64 = self
+            .domains
+            .iter()
+            .zip(start.0.iter().zip(&end.0))
+            .map(|(d, (&s, &e))| {
+                if e >= s {
+                    e - s
+                } else {
+                    d.max_energy_uj - s + e
+                }
+            })
+            .sum();
+        microjoules as f64 / 1e6
+    }
+}
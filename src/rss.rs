@@ -0,0 +1,19 @@
+//! Peak resident set size from procfs. Linux only; elsewhere both functions
+//! report failure and the RSS columns stay empty.
+
+use std::fs;
+
+/// Resets the process's peak RSS to its current RSS (Linux ≥ 4.0), so the
+/// next [`peak_bytes`] covers only what happens from here on.
+pub fn reset_peak() -> bool {
+    fs::write("/proc/self/clear_refs", "5").is_ok()
+}
+
+/// `VmHWM` from `/proc/self/status`: the peak RSS since start-up or the last
+/// [`reset_peak`].
+pub fn peak_bytes() -> Option<u64> {
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|l| l.starts_with("VmHWM:"))?;
+    let kib: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kib * 1024)
+}
@@ -0,0 +1,207 @@
+//! Multi-threaded scaling benchmark: every generator runs as independent
+//! instances on 1, 2, 4, … threads (up to the available parallelism), each
+//! thread with its own seed, to show how aggregate throughput grows under
+//! contention for shared units such as AES-NI or the memory bus.
+
+use crate::config::Config;
+use crate::{file_slug, throughput_mb_s};
+use plotters::prelude::*;
+use std::error::Error;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+use std::sync::Barrier;
+use std::thread;
+use std::time::Instant;
+
+// Size of each request and number of requests per thread.
+const REQUEST_BITS: usize = 1_000_000;
+const REQUESTS_PER_THREAD: usize = 20;
+
+pub struct ScalingSample {
+    pub generator: String,
+    pub cryptographic: bool,
+    pub threads: usize,
+    /// From the common start until the last thread finished.
+    pub wall_ms: f64,
+    pub aggregate_throughput_mb_s: f64,
+    /// Per-request latency over all threads.
+    pub mean_latency_ms: f64,
+    pub max_latency_ms: f64,
+}
+
+/// 1, 2, 4, … up to and including the available parallelism.
+fn thread_counts() -> Vec<usize> {
+    let max = thread::available_parallelism().map_or(1, |n| n.get());
+    let mut counts: Vec<usize> = (0..).map(|i| 1 << i).take_while(|&n| n < max).collect();
+    counts.push(max);
+    counts
+}
+
+/// Runs the scaling benchmark for every generator and thread count.
+pub fn run(config: &Config) -> Vec<ScalingSample> {
+    let generators = config.build_generators(&config.seed_for(0, REQUEST_BITS));
+    let mut samples = Vec::new();
+    for (index, drbg) in generators.iter().enumerate() {
+        for threads in thread_counts() {
+            samples.push(measure(
+                config,
+                index,
+                threads,
+                drbg.name(),
+                drbg.is_cryptographic(),
+            ));
+        }
+    }
+    samples
+}
+
+/// Each thread builds its own instance of generator `index`, then all start
+/// generating together once every instance is ready.
+fn measure(
+    config: &Config,
+    index: usize,
+    threads: usize,
+    name: &str,
+    cryptographic: bool,
+) -> ScalingSample {
+    let barrier = Barrier::new(threads + 1);
+    let (wall_ms, latencies) = thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|t| {
+                let barrier = &barrier;
+                scope.spawn(move || {
+                    let mut drbg = config
+                        .build_generators(&config.seed_for(t, REQUEST_BITS))
+                        .swap_remove(index);
+                    barrier.wait();
+                    (0..REQUESTS_PER_THREAD)
+                        .map(|_| {
+                            let start = Instant::now();
+                            drbg.generate_bits(REQUEST_BITS);
+                            start.elapsed().as_secs_f64() * 1_000.0
+                        })
+                        .collect::<Vec<f64>>()
+                })
+            })
+            .collect();
+        barrier.wait();
+        let start = Instant::now();
+        let latencies: Vec<f64> = workers
+            .into_iter()
+            .flat_map(|w| w.join().expect("scaling worker panicked"))
+            .collect();
+        (start.elapsed().as_secs_f64() * 1_000.0, latencies)
+    });
+
+    let total_bits = REQUEST_BITS * REQUESTS_PER_THREAD * threads;
+    ScalingSample {
+        generator: name.to_string(),
+        cryptographic,
+        threads,
+        wall_ms,
+        aggregate_throughput_mb_s: throughput_mb_s(total_bits, wall_ms),
+        mean_latency_ms: latencies.iter().sum::<f64>() / latencies.len() as f64,
+        max_latency_ms: latencies.iter().copied().fold(0.0, f64::max),
+    }
+}
+
+pub fn write_csv(dir: &Path, samples: &[ScalingSample]) -> Result<(), Box<dyn Error>> {
+    let mut file = File::create(dir.join("scaling.csv"))?;
+    writeln!(
+        file,
+        "generator,cryptographic,threads,request_bits,requests_per_thread,wall_ms,aggregate_throughput_mb_s,speedup,mean_latency_ms,max_latency_ms"
+    )?;
+    for s in samples {
+        let single = samples
+            .iter()
+            .find(|o| o.generator == s.generator && o.threads == 1)
+            .map_or(s.aggregate_throughput_mb_s, |o| o.aggregate_throughput_mb_s);
+        writeln!(
+            file,
+            "{},{},{},{},{},{:.3},{:.3},{:.3},{:.6},{:.6}",
+            s.generator,
+            s.cryptographic,
+            s.threads,
+            REQUEST_BITS,
+            REQUESTS_PER_THREAD,
+            s.wall_ms,
+            s.aggregate_throughput_mb_s,
+            s.aggregate_throughput_mb_s / single,
+            s.mean_latency_ms,
+            s.max_latency_ms
+        )?;
+    }
+    Ok(())
+}
+
+/// One plot per generator in `dir`: aggregate throughput against thread
+/// count, with perfect linear scaling from one thread drawn for reference.
+pub fn plot(samples: &[ScalingSample], dir: &Path) -> Result<(), Box<dyn Error>> {
+    fs::create_dir_all(dir)?;
+    let mut names: Vec<&str> = samples.iter().map(|s| s.generator.as_str()).collect();
+    names.dedup();
+    for name in names {
+        let series: Vec<&ScalingSample> = samples.iter().filter(|s| s.generator == name).collect();
+        let single = series[0].aggregate_throughput_mb_s;
+        let max_threads = series.iter().map(|s| s.threads).max().unwrap_or(1);
+        let y_max = series
+            .iter()
+            .map(|s| s.aggregate_throughput_mb_s)
+            .fold(single * max_threads as f64, f64::max)
+            * 1.1;
+
+        let path = dir.join(format!("{}.png", file_slug(name)));
+        let root = BitMapBackend::new(&path, (900, 600)).into_drawing_area();
+        root.fill(&WHITE)?;
+        let mut chart = ChartBuilder::on(&root)
+            .caption(
+                format!("{name}: multi-threaded scaling"),
+                ("sans-serif", 26).into_font(),
+            )
+            .margin(20)
+            .x_label_area_size(50)
+            .y_label_area_size(90)
+            .build_cartesian_2d(1usize..max_threads.max(2), 0f64..y_max)?;
+
+        chart
+            .configure_mesh()
+            .x_desc("Threads")
+            .y_desc("Aggregate MB/s")
+            .label_style(("sans-serif", 16))
+            .axis_desc_style(("sans-serif", 18))
+            .draw()?;
+
+        chart
+            .draw_series(LineSeries::new(
+                [(1, single), (max_threads, single * max_threads as f64)],
+                BLACK.mix(0.4).stroke_width(1),
+            ))?
+            .label("Linear scaling")
+            .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 25, y)], BLACK.mix(0.4)));
+        chart
+            .draw_series(LineSeries::new(
+                series
+                    .iter()
+                    .map(|s| (s.threads, s.aggregate_throughput_mb_s)),
+                BLUE.stroke_width(3),
+            ))?
+            .label("Measured")
+            .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 25, y)], BLUE.stroke_width(3)));
+        chart.draw_series(
+            series
+                .iter()
+                .map(|s| Circle::new((s.threads, s.aggregate_throughput_mb_s), 4, BLUE.filled())),
+        )?;
+
+        chart
+            .configure_series_labels()
+            .position(SeriesLabelPosition::UpperLeft)
+            .background_style(WHITE.mix(0.8))
+            .border_style(BLACK)
+            .label_font(("sans-serif", 16))
+            .draw()?;
+        root.present()?;
+    }
+    Ok(())
+}
@@ -0,0 +1,175 @@
+//! Seed-sweep benchmark: the measurements of the sweep (sequential fills
+//! only) repeated under several independent base seeds, so that a battery
+//! failure or a timing can be told apart from the one seed derivation chain
+//! of a normal sweep. Results are summarised per seed and over all seeds
+//! and runs together.
+
+use crate::config::{Config, to_hex};
+use crate::{
+    Instruments, Summary, battery, battery_verdicts, mark_outliers, mean, measure, optional,
+    progress_bar, stddev, summarize, warm_up,
+};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use tracing::{debug_span, info_span};
+
+/// One (generator, length) configuration under one base seed.
+pub struct SeedSample {
+    pub index: usize,
+    pub seed: Vec<u8>,
+    pub summary: Summary,
+    /// Battery tests failing after the Benjamini–Hochberg correction.
+    pub failed_tests: usize,
+}
+
+pub struct SeedSweep {
+    pub samples: Vec<SeedSample>,
+    /// Every seed's runs analysed as one sample, with its failing tests.
+    pub pooled: Vec<(Summary, usize)>,
+}
+
+fn failed_tests(
+    verdicts: &BTreeMap<(String, usize), Vec<battery::Verdict>>,
+    summary: &Summary,
+) -> usize {
+    verdicts
+        .get(&(summary.generator.clone(), summary.bits))
+        .map_or(0, |v| v.iter().filter(|v| !v.adjusted_pass).count())
+}
+
+pub fn run(config: &Config, seeds: usize) -> SeedSweep {
+    info_span!("warmup").in_scope(|| warm_up(config));
+    let constructors = config.constructors();
+    let progress = progress_bar(
+        (seeds * config.runs * config.lengths.iter().sum::<usize>() * constructors.len()) as u64,
+    );
+    let mut samples = Vec::new();
+    let mut pooled = Vec::new();
+    for index in 0..seeds {
+        let _span = info_span!("seed", index).entered();
+        let mut seeded = config.clone();
+        seeded.seed = config.sweep_seed(index);
+        let mut records = Vec::new();
+        for run in 0..config.runs {
+            for &bits in &config.lengths {
+                for new in &constructors {
+                    let Some(mut drbg) = new(&seeded.seed_for(run, bits)) else {
+                        progress.inc(bits as u64);
+                        continue;
+                    };
+                    progress.set_message(format!("seed {index}: {} at {bits} bits", drbg.name()));
+                    let _span =
+                        debug_span!("measure", generator = drbg.name(), bits, run).entered();
+                    let name = drbg.name().to_string();
+                    let cryptographic = drbg.is_cryptographic();
+                    records.extend(measure(
+                        run,
+                        name,
+                        cryptographic,
+                        bits,
+                        &mut Instruments::default(),
+                        config.tests,
+                        || Some(drbg.generate_bits(bits)),
+                    ));
+                    progress.inc(bits as u64);
+                }
+            }
+        }
+        mark_outliers(&mut records, config.outlier_mads);
+        let verdicts = battery_verdicts(&records);
+        for summary in summarize(&records, &verdicts) {
+            samples.push(SeedSample {
+                index,
+                seed: seeded.seed.clone(),
+                failed_tests: failed_tests(&verdicts, &summary),
+                summary,
+            });
+        }
+        pooled.extend(records);
+    }
+    progress.finish_and_clear();
+
+    // Grouping is by generator and length only, so the repeated run
+    // indices of different seeds do not collide.
+    mark_outliers(&mut pooled, config.outlier_mads);
+    let verdicts = battery_verdicts(&pooled);
+    let pooled = summarize(&pooled, &verdicts)
+        .into_iter()
+        .map(|summary| {
+            let failed = failed_tests(&verdicts, &summary);
+            (summary, failed)
+        })
+        .collect();
+    SeedSweep { samples, pooled }
+}
+
+/// Writes `seed_sweep.csv`, one row per configuration and seed, and
+/// `seed_summary.csv`, one row per configuration: the spread of the
+/// per-seed means across seeds, and the battery over all seeds' runs.
+pub fn write_csv(dir: &Path, sweep: &SeedSweep) -> Result<(), Box<dyn Error>> {
+    let mut file = File::create(dir.join("seed_sweep.csv"))?;
+    writeln!(
+        file,
+        "seed_index,seed,generator,cryptographic,bits,runs,mean_time_ms,median_time_ms,mean_ones_ratio,min_uniformity_p,failed_tests"
+    )?;
+    for s in &sweep.samples {
+        writeln!(
+            file,
+            "{},{},{},{},{},{},{:.6},{:.6},{:.6},{},{}",
+            s.index,
+            to_hex(&s.seed),
+            s.summary.generator,
+            s.summary.cryptographic,
+            s.summary.bits,
+            s.summary.runs,
+            s.summary.mean_time_ms,
+            s.summary.median_time_ms,
+            s.summary.mean_ones_ratio,
+            optional(s.summary.min_uniformity_p),
+            s.failed_tests
+        )?;
+    }
+
+    let mut file = File::create(dir.join("seed_summary.csv"))?;
+    writeln!(
+        file,
+        "generator,cryptographic,bits,seeds,runs,mean_time_ms,std_time_ms_across_seeds,mean_ones_ratio,std_ones_ratio_across_seeds,min_uniformity_p,failed_tests,seeds_failing,worst_seed_uniformity_p"
+    )?;
+    for (pooled, failed) in &sweep.pooled {
+        let per_seed: Vec<&SeedSample> = sweep
+            .samples
+            .iter()
+            .filter(|s| s.summary.generator == pooled.generator && s.summary.bits == pooled.bits)
+            .collect();
+        let times: Vec<f64> = per_seed.iter().map(|s| s.summary.mean_time_ms).collect();
+        let ratios: Vec<f64> = per_seed.iter().map(|s| s.summary.mean_ones_ratio).collect();
+        let mean_time = mean(times.iter().copied());
+        let mean_ratio = mean(ratios.iter().copied());
+        writeln!(
+            file,
+            "{},{},{},{},{},{:.6},{:.6},{:.6},{:.6},{},{},{},{}",
+            pooled.generator,
+            pooled.cryptographic,
+            pooled.bits,
+            per_seed.len(),
+            pooled.runs,
+            mean_time,
+            stddev(times.into_iter(), mean_time),
+            mean_ratio,
+            stddev(ratios.into_iter(), mean_ratio),
+            optional(pooled.min_uniformity_p),
+            failed,
+            per_seed.iter().filter(|s| s.failed_tests > 0).count(),
+            optional(
+                per_seed
+                    .iter()
+                    .filter_map(|s| s.summary.min_uniformity_p)
+                    .reduce(f64::min)
+            )
+        )?;
+    }
+    Ok(())
+}
@@ -0,0 +1,284 @@
+//! Results store: every sweep appends its records, per-test p-values and
+//! summaries to `results.sqlite` in the output directory, so sessions
+//! accumulate and can be compared with SQL instead of concatenated CSVs.
+//!
+//! Tables: `environments` (one row per distinct `environment.json`), `runs`
+//! (one row per sweep), `records` (one per measurement, with `repetition` the
+//! run index of `metrics.csv`), `tests` (one p-value per record and test),
+//! `summaries`. Files from older versions are brought up to date through
+//! `MIGRATIONS`, tracked in `PRAGMA user_version`.
+
+use crate::config::{Config, to_hex};
+use crate::host::Environment;
+use crate::{Record, Summary};
+use rusqlite::{Connection, OptionalExtension, params};
+use std::error::Error;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Schema changes in order; a file at `user_version` n has had the first n
+/// applied. The first is written so that it also runs on files that predate
+/// the version tracking.
+const MIGRATIONS: [&str; 3] = [
+    "
+CREATE TABLE IF NOT EXISTS environments (
+    id INTEGER PRIMARY KEY,
+    os TEXT NOT NULL,
+    arch TEXT NOT NULL,
+    cpus INTEGER NOT NULL,
+    pinned_core INTEGER,
+    scaling_governor TEXT,
+    turbo INTEGER
+);
+CREATE TABLE IF NOT EXISTS runs (
+    id INTEGER PRIMARY KEY,
+    started_at INTEGER NOT NULL,
+    environment_id INTEGER NOT NULL REFERENCES environments (id),
+    seed TEXT NOT NULL,
+    repetitions INTEGER NOT NULL,
+    lengths TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS records (
+    id INTEGER PRIMARY KEY,
+    run_id INTEGER NOT NULL REFERENCES runs (id),
+    repetition INTEGER NOT NULL,
+    generator TEXT NOT NULL,
+    cryptographic INTEGER NOT NULL,
+    bits INTEGER NOT NULL,
+    duration_ms REAL NOT NULL,
+    throughput_mb_s REAL NOT NULL,
+    bits_per_ns REAL NOT NULL,
+    cycles INTEGER,
+    outlier INTEGER NOT NULL,
+    storage_bytes INTEGER NOT NULL,
+    allocations INTEGER NOT NULL,
+    allocated_bytes INTEGER NOT NULL,
+    peak_rss_bytes INTEGER,
+    instructions INTEGER,
+    cache_misses INTEGER,
+    branch_misses INTEGER,
+    energy_j REAL,
+    zeros INTEGER NOT NULL,
+    ones INTEGER NOT NULL,
+    min_entropy REAL,
+    shannon_entropy REAL NOT NULL,
+    compression_ratio REAL NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tests (
+    record_id INTEGER NOT NULL REFERENCES records (id),
+    test TEXT NOT NULL,
+    p_value REAL
+);
+CREATE INDEX IF NOT EXISTS tests_by_test ON tests (test);
+CREATE TABLE IF NOT EXISTS summaries (
+    run_id INTEGER NOT NULL REFERENCES runs (id),
+    generator TEXT NOT NULL,
+    cryptographic INTEGER NOT NULL,
+    bits INTEGER NOT NULL,
+    repetitions INTEGER NOT NULL,
+    outliers INTEGER NOT NULL,
+    mean_time_ms REAL NOT NULL,
+    std_time_ms REAL NOT NULL,
+    time_ci_ms REAL NOT NULL,
+    median_time_ms REAL NOT NULL,
+    p5_time_ms REAL NOT NULL,
+    p95_time_ms REAL NOT NULL,
+    min_time_ms REAL NOT NULL,
+    max_time_ms REAL NOT NULL,
+    mean_throughput_mb_s REAL NOT NULL,
+    mean_cycles_per_byte REAL,
+    mean_energy_nj_per_byte REAL,
+    mean_ones_ratio REAL NOT NULL,
+    mean_min_entropy REAL,
+    min_uniformity_p REAL,
+    min_ks_p REAL,
+    storage_bytes INTEGER NOT NULL,
+    peak_rss_bytes INTEGER
+);
+",
+    // `environment.json` fields and its id, which rows are now matched on.
+    "
+ALTER TABLE environments ADD COLUMN fingerprint TEXT;
+ALTER TABLE environments ADD COLUMN cpu_model TEXT;
+ALTER TABLE environments ADD COLUMN os_release TEXT;
+ALTER TABLE environments ADD COLUMN rustc TEXT;
+ALTER TABLE environments ADD COLUMN git_commit TEXT;
+ALTER TABLE environments ADD COLUMN cpu_features TEXT;
+CREATE UNIQUE INDEX environments_by_fingerprint ON environments (fingerprint);
+",
+    // `--tag`.
+    "
+ALTER TABLE records ADD COLUMN tag TEXT;
+ALTER TABLE summaries ADD COLUMN tag TEXT;
+",
+];
+
+/// Appends one sweep to `dir/results.sqlite`, creating the file and tables
+/// on first use, in a single transaction; returns the new run's id.
+pub fn append(
+    dir: &Path,
+    config: &Config,
+    started_at: SystemTime,
+    environment: &Environment,
+    records: &[Record],
+    summaries: &[Summary],
+) -> Result<i64, Box<dyn Error>> {
+    let mut connection = Connection::open(dir.join("results.sqlite"))?;
+    let transaction = connection.transaction()?;
+    migrate(&transaction)?;
+
+    let environment_id = environment_id(&transaction, environment)?;
+    let lengths: Vec<String> = config.lengths.iter().map(usize::to_string).collect();
+    transaction.execute(
+        "INSERT INTO runs (started_at, environment_id, seed, repetitions, lengths)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![
+            started_at.duration_since(UNIX_EPOCH)?.as_secs(),
+            environment_id,
+            to_hex(&config.seed),
+            config.runs,
+            lengths.join(","),
+        ],
+    )?;
+    let run_id = transaction.last_insert_rowid();
+
+    {
+        let mut insert_record = transaction.prepare(
+            "INSERT INTO records (run_id, repetition, generator, cryptographic, bits,
+                 duration_ms, throughput_mb_s, bits_per_ns, cycles, outlier, storage_bytes,
+                 allocations, allocated_bytes, peak_rss_bytes, instructions, cache_misses,
+                 branch_misses, energy_j, zeros, ones, min_entropy, shannon_entropy,
+                 compression_ratio, tag)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16,
+                 ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24)",
+        )?;
+        let mut insert_test = transaction
+            .prepare("INSERT INTO tests (record_id, test, p_value) VALUES (?1, ?2, ?3)")?;
+        for r in records {
+            insert_record.execute(params![
+                run_id,
+                r.run,
+                r.generator,
+                r.cryptographic,
+                r.bits,
+                r.duration_ms,
+                r.throughput_mb_s,
+                r.bits_per_ns,
+                r.cycles,
+                r.outlier,
+                r.storage_bytes,
+                r.allocations,
+                r.allocated_bytes,
+                r.peak_rss_bytes,
+                r.perf.map(|p| p.instructions),
+                r.perf.map(|p| p.cache_misses),
+                r.perf.map(|p| p.branch_misses),
+                r.energy_j,
+                r.zeros,
+                r.ones,
+                r.min_entropy.map(|e| e.min()),
+                r.ent.entropy,
+                r.compression_ratio,
+                r.tag,
+            ])?;
+            let record_id = transaction.last_insert_rowid();
+            for (test, p_value) in crate::p_values(r) {
+                insert_test.execute(params![record_id, test, p_value])?;
+            }
+        }
+
+        let mut insert_summary = transaction.prepare(
+            "INSERT INTO summaries (run_id, generator, cryptographic, bits, repetitions,
+                 outliers, mean_time_ms, std_time_ms, time_ci_ms, median_time_ms, p5_time_ms,
+                 p95_time_ms, min_time_ms, max_time_ms, mean_throughput_mb_s,
+                 mean_cycles_per_byte, mean_energy_nj_per_byte, mean_ones_ratio,
+                 mean_min_entropy, min_uniformity_p, min_ks_p, storage_bytes, peak_rss_bytes,
+                 tag)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16,
+                 ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24)",
+        )?;
+        for s in summaries {
+            insert_summary.execute(params![
+                run_id,
+                s.generator,
+                s.cryptographic,
+                s.bits,
+                s.runs,
+                s.outliers,
+                s.mean_time_ms,
+                s.std_time_ms,
+                s.time_ci_ms,
+                s.median_time_ms,
+                s.p5_time_ms,
+                s.p95_time_ms,
+                s.min_time_ms,
+                s.max_time_ms,
+                s.mean_throughput_mb_s,
+                s.mean_cycles_per_byte,
+                s.mean_energy_nj_per_byte,
+                s.mean_ones_ratio,
+                s.mean_min_entropy,
+                s.min_uniformity_p,
+                s.min_ks_p,
+                s.storage_bytes,
+                s.peak_rss_bytes,
+                s.tag,
+            ])?;
+        }
+    }
+
+    transaction.commit()?;
+    Ok(run_id)
+}
+
+fn migrate(connection: &Connection) -> Result<(), Box<dyn Error>> {
+    let version: usize = connection.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+    if version > MIGRATIONS.len() {
+        return Err(format!(
+            "results.sqlite has schema version {version}, newer than this build supports"
+        )
+        .into());
+    }
+    for migration in &MIGRATIONS[version..] {
+        connection.execute_batch(migration)?;
+    }
+    connection.pragma_update(None, "user_version", MIGRATIONS.len())?;
+    Ok(())
+}
+
+/// Id of the row for `environment`, inserted if there is none yet.
+fn environment_id(
+    connection: &Connection,
+    environment: &Environment,
+) -> Result<i64, Box<dyn Error>> {
+    let existing = connection
+        .query_row(
+            "SELECT id FROM environments WHERE fingerprint = ?1",
+            [&environment.id],
+            |row| row.get(0),
+        )
+        .optional()?;
+    if let Some(id) = existing {
+        return Ok(id);
+    }
+    connection.execute(
+        "INSERT INTO environments (fingerprint, os, os_release, arch, cpu_model, cpus,
+             pinned_core, scaling_governor, turbo, rustc, git_commit, cpu_features)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+        params![
+            environment.id,
+            environment.os,
+            environment.os_release,
+            environment.arch,
+            environment.cpu_model,
+            environment.cpus,
+            environment.pinned_core,
+            environment.scaling_governor,
+            environment.turbo,
+            environment.rustc,
+            environment.git_commit,
+            environment.cpu_features.join(","),
+        ],
+    )?;
+    Ok(connection.last_insert_rowid())
+}
@@ -0,0 +1,1800 @@
+use crate::drbg::BitString;
+use rustfft::FftPlanner;
+use rustfft::num_complex::Complex;
+use serde::{Deserialize, Serialize};
+
+/// Significance level α used for pass/fail decisions, as in the STS defaults.
+pub const SIGNIFICANCE_LEVEL: f64 = 0.01;
+
+// Cephes constants used by the incomplete gamma continued fraction.
+const MACHEP: f64 = 1.110_223_024_625_156_5e-16;
+const MAXLOG: f64 = 7.097_827_128_933_84e2;
+const BIG: f64 = 4.503_599_627_370_496e15;
+const BIG_INV: f64 = 2.220_446_049_250_313e-16;
+// SP 800-22 §2.4 parameters for the longest-run test, keyed by the minimum
+// sequence length: block size M, lowest class v_0, and class probabilities π_i
+// (at the precision used by the STS reference implementation).
+const LONGEST_RUN_TABLE: [(usize, usize, usize, &[f64]); 3] = [
+    (
+        750_000,
+        10_000,
+        10,
+        &[0.0882, 0.2092, 0.2483, 0.1933, 0.1208, 0.0675, 0.0727],
+    ),
+    (
+        6_272,
+        128,
+        4,
+        &[
+            0.117_403_578_8,
+            0.242_955_959,
+            0.249_363_483,
+            0.175_177_06,
+            0.102_701_071,
+            0.112_398_847,
+        ],
+    ),
+    (
+        128,
+        8,
+        1,
+        &[0.214_843_75, 0.367_187_5, 0.230_468_75, 0.1875],
+    ),
+];
+// Square matrix dimension M = Q for the binary matrix rank test.
+const RANK_MATRIX_DIM: usize = 32;
+// Template length m and block count N for the non-overlapping template test.
+pub const TEMPLATE_LENGTH: usize = 9;
+const TEMPLATE_BLOCKS: usize = 8;
+// Overlapping template test: all-ones template of length m and block size M,
+// and the probabilities of 0, 1, …, 4 and ≥ 5 matches per block for m = 9,
+// M = 1032 (η = 1) from Hamano and Kaneko's exact computation, as used by
+// SP 800-22 rev. 1a and STS 2.1.2. The compound-Poisson approximation of the
+// original §3.8 is off by enough to bias the p-values low at 10⁷ bits.
+const OVERLAPPING_TEMPLATE_LENGTH: usize = 9;
+const OVERLAPPING_BLOCK_LEN: usize = 1032;
+const OVERLAPPING_PROBABILITIES: [f64; 6] =
+    [0.364091, 0.185659, 0.139381, 0.100571, 0.070432, 0.139865];
+// Maurer's universal test (SP 800-22 §2.9): the smallest sequence length for
+// each block length L = 6..16, and the expected value and variance of f_n for
+// L = 0..16.
+const MAURER_MIN_LENGTHS: [usize; 11] = [
+    387_840,
+    904_960,
+    2_068_480,
+    4_654_080,
+    10_342_400,
+    22_753_280,
+    49_643_520,
+    107_560_960,
+    231_669_760,
+    496_435_200,
+    1_059_061_760,
+];
+const MAURER_EXPECTED: [f64; 17] = [
+    0.0,
+    0.732_649_5,
+    1.537_438_3,
+    2.401_606_8,
+    3.311_224_7,
+    4.253_426_6,
+    5.217_705_2,
+    6.196_250_7,
+    7.183_665_6,
+    8.176_424_8,
+    9.172_324_3,
+    10.170_032,
+    11.168_765,
+    12.168_070,
+    13.167_693,
+    14.167_488,
+    15.167_379,
+];
+const MAURER_VARIANCE: [f64; 17] = [
+    0.0, 0.690, 1.338, 1.901, 2.358, 2.705, 2.954, 3.125, 3.238, 3.311, 3.356, 3.384, 3.401, 3.410,
+    3.416, 3.419, 3.421,
+];
+// Linear complexity test: block length M and the class probabilities π_0..π_6.
+const LINEAR_COMPLEXITY_BLOCK_LEN: usize = 500;
+const LINEAR_COMPLEXITY_PI: [f64; 7] = [0.010_417, 0.031_25, 0.125, 0.5, 0.25, 0.062_5, 0.020_833];
+// Random excursions (variant) test states and the minimum number of cycles J.
+pub const EXCURSION_STATES: [i64; 8] = [-4, -3, -2, -1, 1, 2, 3, 4];
+pub const EXCURSION_VARIANT_STATES: [i64; 18] = [
+    -9, -8, -7, -6, -5, -4, -3, -2, -1, 1, 2, 3, 4, 5, 6, 7, 8, 9,
+];
+const EXCURSION_MIN_CYCLES: usize = 500;
+// Diehard birthday spacings: m = 512 birthdays in a year of n = 2^24 days, so
+// repeated spacings are Poisson with λ = m³/(4n) = 2. Each 64-bit word yields
+// a birthday at every offset, as Diehard tests several bit fields.
+const BIRTHDAY_COUNT: usize = 512;
+const BIRTHDAY_DAY_BITS: u32 = 24;
+const BIRTHDAY_OFFSETS: [u32; 6] = [0, 8, 16, 24, 32, 40];
+const BIRTHDAY_LAMBDA: f64 = 2.0;
+// Knuth's gap test on uniforms from 32-bit words: interval [α, β) and the
+// gap length t from which gaps are pooled into one class.
+const GAP_ALPHA: f64 = 0.3;
+const GAP_BETA: f64 = 0.6;
+const GAP_CLASSES: usize = 10;
+// Overlapping permutations of this many consecutive 32-bit words.
+const PERMUTATION_LEN: usize = 5;
+// Smallest expected count per class for the chi-square approximations.
+const MIN_EXPECTED_COUNT: f64 = 5.0;
+// ENT's Monte Carlo π: 6-byte groups give 24-bit x and y coordinates.
+const MONTE_CARLO_BYTES: usize = 6;
+// zstd level for the compression-ratio metric: fast, as a cheap screen.
+const ZSTD_LEVEL: i32 = 1;
+// Longest run of ones inside each byte value, for the byte-wise scan.
+const LONGEST_RUN_IN_BYTE: [u8; 256] = longest_run_in_byte_table();
+// Lanczos approximation (g = 7, n = 9) for ln Γ.
+const LANCZOS_G: f64 = 7.0;
+const LANCZOS_COEFFICIENTS: [f64; 9] = [
+    0.999_999_999_999_809_9,
+    676.520_368_121_885_1,
+    -1_259.139_216_722_402_8,
+    771.323_428_777_653_1,
+    -176.615_029_162_140_6,
+    12.507_343_278_686_905,
+    -0.138_571_095_265_720_12,
+    9.984_369_578_019_572e-6,
+    1.505_632_735_149_311_6e-7,
+];
+
+/// NIST SP 800-22 §2.1 frequency (monobit) test: the p-value of the
+/// normalized excess of ones over zeros.
+pub fn monobit(bits: &BitString) -> f64 {
+    if bits.bits == 0 {
+        return 1.0;
+    }
+    let tally = bits.count_bits();
+    let s = tally.ones as f64 - tally.zeros as f64;
+    let s_obs = s.abs() / (bits.bits as f64).sqrt();
+    erfc(s_obs / std::f64::consts::SQRT_2)
+}
+
+/// NIST SP 800-22 §2.2 frequency test within a block: a chi-square over the
+/// proportion of ones in each of the ⌊n/M⌋ non-overlapping M-bit blocks.
+pub fn block_frequency(bits: &BitString, block_size: usize) -> f64 {
+    let blocks = bits.bits / block_size;
+    if blocks == 0 {
+        return 1.0;
+    }
+    let mut chi_squared = 0.0;
+    for block in 0..blocks {
+        let start = block * block_size;
+        let ones: usize = (start..start + block_size)
+            .map(|i| bits.bit(i) as usize)
+            .sum();
+        let pi = ones as f64 / block_size as f64 - 0.5;
+        chi_squared += pi * pi;
+    }
+    chi_squared *= 4.0 * block_size as f64;
+    igamc(blocks as f64 / 2.0, chi_squared / 2.0)
+}
+
+/// Outcome of the SP 800-22 runs test.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct RunsResult {
+    /// Total number of runs V_n(obs) of identical bits.
+    pub runs: u64,
+    pub p_value: f64,
+}
+
+/// NIST SP 800-22 §2.3 runs test. A sequence that already fails the frequency
+/// prerequisite |π - 1/2| < 2/√n gets a p-value of 0, as in the reference code.
+pub fn runs(bits: &BitString) -> RunsResult {
+    let n = bits.bits;
+    if n == 0 {
+        return RunsResult {
+            runs: 0,
+            p_value: 1.0,
+        };
+    }
+
+    // Count bit transitions a byte at a time; runs = transitions + 1.
+    let full_bytes = n / 8;
+    let mut transitions = 0u64;
+    let mut previous: Option<u8> = None;
+    for &byte in &bits.bytes[..full_bytes] {
+        transitions += ((byte ^ (byte >> 1)) & 0x7F).count_ones() as u64;
+        if let Some(last) = previous {
+            transitions += ((last ^ (byte >> 7)) & 1) as u64;
+        }
+        previous = Some(byte);
+    }
+    for i in full_bytes * 8..n {
+        let bit = bits.bit(i);
+        if let Some(last) = previous {
+            transitions += ((last ^ bit) & 1) as u64;
+        }
+        previous = Some(bit);
+    }
+    let runs = transitions + 1;
+
+    let pi = bits.count_bits().ones as f64 / n as f64;
+    let p_value = if (pi - 0.5).abs() >= 2.0 / (n as f64).sqrt() {
+        0.0
+    } else {
+        let n = n as f64;
+        let expected = 2.0 * n * pi * (1.0 - pi);
+        erfc((runs as f64 - expected).abs() / (2.0 * (2.0 * n).sqrt() * pi * (1.0 - pi)))
+    };
+    RunsResult { runs, p_value }
+}
+
+/// NIST SP 800-22 §2.4 longest-run-of-ones-in-a-block test, with M, K and the
+/// class probabilities taken from the standard table for the sequence length.
+pub fn longest_run(bits: &BitString) -> f64 {
+    let Some(&(_, block_size, v_min, pi)) = LONGEST_RUN_TABLE
+        .iter()
+        .find(|(min_len, ..)| bits.bits >= *min_len)
+    else {
+        return 1.0;
+    };
+    let classes = pi.len();
+    let blocks = bits.bits / block_size;
+    let mut counts = vec![0u64; classes];
+    for block in bits.bytes[..blocks * block_size / 8].chunks(block_size / 8) {
+        let (mut longest, mut current) = (0usize, 0usize);
+        for &byte in block {
+            if byte == 0xFF {
+                current += 8;
+                continue;
+            }
+            longest = longest.max(current + byte.leading_ones() as usize);
+            longest = longest.max(LONGEST_RUN_IN_BYTE[byte as usize] as usize);
+            current = byte.trailing_ones() as usize;
+        }
+        longest = longest.max(current);
+        counts[longest.clamp(v_min, v_min + classes - 1) - v_min] += 1;
+    }
+
+    let n = blocks as f64;
+    let chi_squared: f64 = counts
+        .iter()
+        .zip(pi)
+        .map(|(&v, &p)| (v as f64 - n * p).powi(2) / (n * p))
+        .sum();
+    igamc((classes - 1) as f64 / 2.0, chi_squared / 2.0)
+}
+
+const fn longest_run_in_byte_table() -> [u8; 256] {
+    let mut table = [0u8; 256];
+    let mut value = 0;
+    while value < 256 {
+        // Each x &= x << 1 shortens every run of ones by one bit.
+        let mut x = value as u8;
+        let mut length = 0;
+        while x != 0 {
+            x &= x << 1;
+            length += 1;
+        }
+        table[value] = length;
+        value += 1;
+    }
+    table
+}
+
+/// NIST SP 800-22 §2.5 binary matrix rank test over disjoint 32×32 matrices,
+/// comparing the counts of full-rank, rank-31 and lower-rank matrices.
+pub fn matrix_rank(bits: &BitString) -> f64 {
+    let matrix_bytes = RANK_MATRIX_DIM * RANK_MATRIX_DIM / 8;
+    let matrices = bits.bits / (8 * matrix_bytes);
+    if matrices == 0 {
+        return 1.0;
+    }
+
+    let mut counts = [0u64; 3];
+    for matrix in bits.bytes[..matrices * matrix_bytes].chunks(matrix_bytes) {
+        let mut rows = [0u32; RANK_MATRIX_DIM];
+        for (row, bytes) in rows.iter_mut().zip(matrix.chunks(4)) {
+            *row = u32::from_be_bytes(bytes.try_into().unwrap());
+        }
+        match RANK_MATRIX_DIM - gf2_rank(&mut rows) {
+            0 => counts[0] += 1,
+            1 => counts[1] += 1,
+            _ => counts[2] += 1,
+        }
+    }
+
+    let full = rank_probability(RANK_MATRIX_DIM);
+    let minus_one = rank_probability(RANK_MATRIX_DIM - 1);
+    let n = matrices as f64;
+    let chi_squared: f64 = counts
+        .iter()
+        .zip([full, minus_one, 1.0 - full - minus_one])
+        .map(|(&f, p)| (f as f64 - n * p).powi(2) / (n * p))
+        .sum();
+    (-chi_squared / 2.0).exp()
+}
+
+/// Rank of a square binary matrix over GF(2), one row per word with column 0 in
+/// the most significant bit. The rows are reduced in place.
+pub fn gf2_rank(rows: &mut [u32]) -> usize {
+    let mut rank = 0;
+    for column in 0..u32::BITS {
+        let mask = 1u32 << (u32::BITS - 1 - column);
+        let Some(pivot) = (rank..rows.len()).find(|&r| rows[r] & mask != 0) else {
+            continue;
+        };
+        rows.swap(rank, pivot);
+        let pivot_row = rows[rank];
+        for row in rows[rank + 1..].iter_mut() {
+            if *row & mask != 0 {
+                *row ^= pivot_row;
+            }
+        }
+        rank += 1;
+        if rank == rows.len() {
+            break;
+        }
+    }
+    rank
+}
+
+/// Probability that a random M×M binary matrix (M = `RANK_MATRIX_DIM`) has rank r.
+fn rank_probability(r: usize) -> f64 {
+    let m = RANK_MATRIX_DIM as i32;
+    let r = r as i32;
+    let mut product = 1.0;
+    for i in 0..r {
+        let row_col = 1.0 - 2f64.powi(i - m);
+        product *= row_col * row_col / (1.0 - 2f64.powi(i - r));
+    }
+    2f64.powi(r * (2 * m - r) - m * m) * product
+}
+
+/// NIST SP 800-22 §2.6 discrete Fourier transform (spectral) test: the share of
+/// the first n/2 DFT magnitudes of the ±1 sequence below the 95% peak threshold.
+pub fn spectral(bits: &BitString) -> f64 {
+    let n = bits.bits;
+    if n == 0 {
+        return 1.0;
+    }
+    let mut buffer: Vec<Complex<f64>> = (0..n)
+        .map(|i| Complex::new(2.0 * bits.bit(i) as f64 - 1.0, 0.0))
+        .collect();
+    FftPlanner::new().plan_fft_forward(n).process(&mut buffer);
+
+    let n = n as f64;
+    let threshold = ((1.0f64 / 0.05).ln() * n).sqrt();
+    let below = buffer[..bits.bits / 2]
+        .iter()
+        .filter(|s| s.norm() < threshold)
+        .count() as f64;
+    let expected = 0.95 * n / 2.0;
+    let d = (below - expected) / (n * 0.95 * 0.05 / 4.0).sqrt();
+    erfc(d.abs() / std::f64::consts::SQRT_2)
+}
+
+/// The aperiodic (non-self-overlapping) m-bit templates in ascending order, as
+/// in the STS template files; there are 148 for m = 9.
+pub fn aperiodic_templates(m: usize) -> Vec<u32> {
+    (0..1u32 << m)
+        .filter(|&t| (1..m).all(|k| t >> k != t & ((1 << (m - k)) - 1)))
+        .collect()
+}
+
+/// NIST SP 800-22 §2.7 non-overlapping template matching test with every
+/// aperiodic 9-bit template over 8 blocks; one p-value per template, in the
+/// order of `aperiodic_templates`.
+pub fn non_overlapping_templates(bits: &BitString) -> Vec<f64> {
+    non_overlapping_template_p(bits, TEMPLATE_LENGTH, TEMPLATE_BLOCKS)
+}
+
+fn non_overlapping_template_p(bits: &BitString, m: usize, blocks: usize) -> Vec<f64> {
+    let templates = aperiodic_templates(m);
+    let block_len = bits.bits / blocks;
+    if block_len < m {
+        return vec![1.0; templates.len()];
+    }
+    // Each m-bit window equals at most one template, so a single pass per block
+    // counts every template at once; `next` enforces the skip after a match.
+    let mut template_index = vec![usize::MAX; 1 << m];
+    for (i, &t) in templates.iter().enumerate() {
+        template_index[t as usize] = i;
+    }
+    let mask = (1usize << m) - 1;
+    let mean = (block_len - m + 1) as f64 / (1u64 << m) as f64;
+    let variance = block_len as f64
+        * (1.0 / (1u64 << m) as f64 - (2 * m - 1) as f64 / (1u64 << (2 * m)) as f64);
+
+    let mut chi_squared = vec![0.0; templates.len()];
+    let mut counts = vec![0u64; templates.len()];
+    let mut next = vec![0usize; templates.len()];
+    for block in 0..blocks {
+        counts.fill(0);
+        next.fill(0);
+        let start = block * block_len;
+        let mut window = 0usize;
+        for i in 0..block_len {
+            window = ((window << 1) | bits.bit(start + i) as usize) & mask;
+            if i + 1 < m {
+                continue;
+            }
+            let position = i + 1 - m;
+            let t = template_index[window];
+            if t != usize::MAX && position >= next[t] {
+                counts[t] += 1;
+                next[t] = position + m;
+            }
+        }
+        for (chi, &w) in chi_squared.iter_mut().zip(&counts) {
+            *chi += (w as f64 - mean).powi(2) / variance;
+        }
+    }
+    chi_squared
+        .iter()
+        .map(|chi| igamc(blocks as f64 / 2.0, chi / 2.0))
+        .collect()
+}
+
+/// NIST SP 800-22 §2.8 overlapping template matching test for the all-ones
+/// 9-bit template over 1032-bit blocks, with the corrected class
+/// probabilities of `OVERLAPPING_PROBABILITIES`.
+pub fn overlapping_template(bits: &BitString) -> f64 {
+    overlapping_template_p(bits, OVERLAPPING_TEMPLATE_LENGTH, OVERLAPPING_BLOCK_LEN)
+}
+
+fn overlapping_template_p(bits: &BitString, m: usize, block_len: usize) -> f64 {
+    let pi = OVERLAPPING_PROBABILITIES;
+    let classes = pi.len() - 1;
+    let blocks = bits.bits / block_len;
+    if blocks == 0 {
+        return 1.0;
+    }
+    let mut counts = vec![0u64; classes + 1];
+    for block in 0..blocks {
+        // Every position that ends a run of at least m ones is one match.
+        let (mut matches, mut run) = (0usize, 0usize);
+        for i in block * block_len..(block + 1) * block_len {
+            run = if bits.bit(i) == 1 { run + 1 } else { 0 };
+            if run >= m {
+                matches += 1;
+            }
+        }
+        counts[matches.min(classes)] += 1;
+    }
+
+    let n = blocks as f64;
+    let chi_squared: f64 = counts
+        .iter()
+        .zip(&pi)
+        .map(|(&v, &p)| (v as f64 - n * p).powi(2) / (n * p))
+        .sum();
+    igamc(classes as f64 / 2.0, chi_squared / 2.0)
+}
+
+/// Maurer's universal statistical test (SP 800-22 §2.9), with L taken from
+/// the standard table and Q = 10·2^L initialization blocks. `None` when the
+/// sequence is shorter than the 387 840 bits needed for L = 6.
+pub fn maurer_universal(bits: &BitString) -> Option<f64> {
+    let l = 5 + MAURER_MIN_LENGTHS
+        .iter()
+        .take_while(|&&min_len| bits.bits >= min_len)
+        .count();
+    if l < 6 {
+        return None;
+    }
+    Some(maurer_universal_p(bits, l, 10 << l))
+}
+
+fn maurer_universal_p(bits: &BitString, l: usize, q: usize) -> f64 {
+    let blocks = bits.bits / l;
+    let k = blocks - q;
+    let block = |i: usize| (0..l).fold(0usize, |acc, j| (acc << 1) | bits.bit(i * l + j) as usize);
+
+    // Last (1-based) block index at which each L-bit pattern was seen.
+    let mut last_seen = vec![0usize; 1 << l];
+    for i in 0..q {
+        last_seen[block(i)] = i + 1;
+    }
+    let mut sum = 0.0;
+    for i in q..blocks {
+        let pattern = block(i);
+        sum += ((i + 1 - last_seen[pattern]) as f64).log2();
+        last_seen[pattern] = i + 1;
+    }
+    let f_n = sum / k as f64;
+
+    let l_f = l as f64;
+    let c = 0.7 - 0.8 / l_f + (4.0 + 32.0 / l_f) * (k as f64).powf(-3.0 / l_f) / 15.0;
+    let sigma = c * (MAURER_VARIANCE[l] / k as f64).sqrt();
+    erfc((f_n - MAURER_EXPECTED[l]).abs() / (std::f64::consts::SQRT_2 * sigma))
+}
+
+/// Outcome of the SP 800-22 linear complexity test.
+#[derive(Debug, Clone)]
+pub struct LinearComplexityResult {
+    /// Shortest LFSR length L_i generating each M-bit block.
+    pub lengths: Vec<usize>,
+    pub p_value: f64,
+}
+
+/// NIST SP 800-22 §2.10 linear complexity test over 500-bit blocks.
+pub fn linear_complexity(bits: &BitString) -> LinearComplexityResult {
+    linear_complexity_with(bits, LINEAR_COMPLEXITY_BLOCK_LEN)
+}
+
+fn linear_complexity_with(bits: &BitString, block_len: usize) -> LinearComplexityResult {
+    let blocks = bits.bits / block_len;
+    let lengths: Vec<usize> = (0..blocks)
+        .map(|block| {
+            let sequence: Vec<u8> = (block * block_len..(block + 1) * block_len)
+                .map(|i| bits.bit(i))
+                .collect();
+            berlekamp_massey(&sequence)
+        })
+        .collect();
+    if blocks == 0 {
+        return LinearComplexityResult {
+            lengths,
+            p_value: 1.0,
+        };
+    }
+
+    let m = block_len as f64;
+    let sign = if block_len.is_multiple_of(2) {
+        1.0
+    } else {
+        -1.0
+    };
+    let mean = m / 2.0 + (9.0 - sign) / 36.0 - (m / 3.0 + 2.0 / 9.0) / 2f64.powf(m);
+    let mut counts = [0u64; 7];
+    for &l in &lengths {
+        let t = sign * (l as f64 - mean) + 2.0 / 9.0;
+        let class = if t <= -2.5 {
+            0
+        } else if t > 2.5 {
+            6
+        } else {
+            (t + 3.5).ceil() as usize - 1
+        };
+        counts[class] += 1;
+    }
+    let n = blocks as f64;
+    let chi_squared: f64 = counts
+        .iter()
+        .zip(LINEAR_COMPLEXITY_PI)
+        .map(|(&v, p)| (v as f64 - n * p).powi(2) / (n * p))
+        .sum();
+    LinearComplexityResult {
+        lengths,
+        p_value: igamc(3.0, chi_squared / 2.0),
+    }
+}
+
+/// Berlekamp–Massey over GF(2): the length of the shortest LFSR producing
+/// `sequence` (one bit per element). The connection polynomials are packed 64
+/// coefficients per word, and the sequence is stored reversed so that each
+/// discrepancy is a word-wise AND against a contiguous window.
+pub fn berlekamp_massey(sequence: &[u8]) -> usize {
+    let n = sequence.len();
+    let words = n / 64 + 2;
+    let mut reversed = vec![0u64; words + 1];
+    for (i, &bit) in sequence.iter().enumerate() {
+        let j = n - 1 - i;
+        reversed[j / 64] |= ((bit & 1) as u64) << (j % 64);
+    }
+    let window = |offset: usize| {
+        let (word, shift) = (offset / 64, offset % 64);
+        let low = reversed.get(word).copied().unwrap_or(0) >> shift;
+        let high = match shift {
+            0 => 0,
+            _ => reversed.get(word + 1).copied().unwrap_or(0) << (64 - shift),
+        };
+        low | high
+    };
+
+    let mut c = vec![0u64; words];
+    let mut b = vec![0u64; words];
+    let mut t = vec![0u64; words];
+    c[0] = 1;
+    b[0] = 1;
+    let (mut l, mut shift) = (0usize, 1usize);
+    for step in 0..n {
+        // d = Σ_{i=0..L} C_i · s_{step-i}, with s_{step-i} at reversed[n-1-step+i].
+        let base = n - 1 - step;
+        let words_used = l / 64 + 1;
+        let mut d = 0u64;
+        for (w, &coefficients) in c.iter().enumerate().take(words_used) {
+            d ^= coefficients & window(base + 64 * w);
+        }
+        if d.count_ones().is_multiple_of(2) {
+            shift += 1;
+            continue;
+        }
+        t.copy_from_slice(&c);
+        // C ^= B · x^shift
+        let (word_shift, bit_shift) = (shift / 64, shift % 64);
+        for w in (word_shift..words).rev() {
+            let mut shifted = b[w - word_shift] << bit_shift;
+            if bit_shift > 0 && w > word_shift {
+                shifted |= b[w - word_shift - 1] >> (64 - bit_shift);
+            }
+            c[w] ^= shifted;
+        }
+        if 2 * l <= step {
+            l = step + 1 - l;
+            b.copy_from_slice(&t);
+            shift = 1;
+        } else {
+            shift += 1;
+        }
+    }
+    l
+}
+
+/// The two p-values of the SP 800-22 serial test.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct SerialResult {
+    /// From ∇ψ²_m = ψ²_m - ψ²_{m-1}.
+    pub p_value_1: f64,
+    /// From ∇²ψ²_m = ψ²_m - 2ψ²_{m-1} + ψ²_{m-2}.
+    pub p_value_2: f64,
+}
+
+/// NIST SP 800-22 §2.11 serial test, with the pattern length chosen as
+/// m = ⌊log2 n⌋ - 3 (capped at 16) to respect m < ⌊log2 n⌋ - 2.
+pub fn serial(bits: &BitString) -> SerialResult {
+    let m = (bits.bits.max(1).ilog2() as usize)
+        .saturating_sub(3)
+        .clamp(3, 16);
+    serial_with(bits, m)
+}
+
+fn serial_with(bits: &BitString, m: usize) -> SerialResult {
+    let n = bits.bits;
+    if n < m {
+        return SerialResult {
+            p_value_1: 1.0,
+            p_value_2: 1.0,
+        };
+    }
+    // The (m-1)- and (m-2)-bit counts are marginals of the cyclic m-bit counts.
+    let counts = cyclic_pattern_counts(bits, m);
+    let psi = |counts: &[u64]| {
+        let sum: f64 = counts.iter().map(|&c| (c as f64).powi(2)).sum();
+        sum * counts.len() as f64 / n as f64 - n as f64
+    };
+    let counts_1 = fold_pattern_counts(&counts);
+    let counts_2 = fold_pattern_counts(&counts_1);
+    let psi_m = psi(&counts);
+    let psi_1 = psi(&counts_1);
+    let psi_2 = if m > 2 { psi(&counts_2) } else { 0.0 };
+
+    let delta_1 = psi_m - psi_1;
+    let delta_2 = psi_m - 2.0 * psi_1 + psi_2;
+    SerialResult {
+        p_value_1: igamc(2f64.powi(m as i32 - 2), delta_1 / 2.0),
+        p_value_2: igamc(2f64.powi(m as i32 - 3), delta_2 / 2.0),
+    }
+}
+
+/// NIST SP 800-22 §2.12 approximate entropy test with block length m; the
+/// sequence should satisfy m < ⌊log2 n⌋ - 5.
+pub fn approximate_entropy(bits: &BitString, m: usize) -> f64 {
+    let n = bits.bits;
+    if n <= m {
+        return 1.0;
+    }
+    let counts = cyclic_pattern_counts(bits, m + 1);
+    let phi = |counts: &[u64]| -> f64 {
+        counts
+            .iter()
+            .filter(|&&c| c > 0)
+            .map(|&c| {
+                let pi = c as f64 / n as f64;
+                pi * pi.ln()
+            })
+            .sum()
+    };
+    let apen = phi(&fold_pattern_counts(&counts)) - phi(&counts);
+    let chi_squared = 2.0 * n as f64 * (std::f64::consts::LN_2 - apen);
+    igamc(2f64.powi(m as i32 - 1), chi_squared / 2.0)
+}
+
+/// Counts of every overlapping m-bit pattern over the sequence extended
+/// cyclically by its first m - 1 bits, indexed with the first bit as the MSB.
+fn cyclic_pattern_counts(bits: &BitString, m: usize) -> Vec<u64> {
+    let n = bits.bits;
+    let mask = (1usize << m) - 1;
+    let mut counts = vec![0u64; 1 << m];
+    let mut window = 0usize;
+    for i in 0..n + m - 1 {
+        window = ((window << 1) | bits.bit(i % n) as usize) & mask;
+        if i + 1 >= m {
+            counts[window] += 1;
+        }
+    }
+    counts
+}
+
+/// Cyclic (m-1)-bit pattern counts from the m-bit ones, dropping the last bit.
+fn fold_pattern_counts(counts: &[u64]) -> Vec<u64> {
+    counts.chunks(2).map(|pair| pair[0] + pair[1]).collect()
+}
+
+/// The forward and backward p-values of the SP 800-22 cumulative sums test.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct CusumResult {
+    pub forward_p: f64,
+    pub backward_p: f64,
+}
+
+/// NIST SP 800-22 §2.13 cumulative sums test on the ±1 random walk, from the
+/// start (mode 0) and from the end (mode 1) of the sequence.
+pub fn cusum(bits: &BitString) -> CusumResult {
+    let n = bits.bits;
+    if n == 0 {
+        return CusumResult {
+            forward_p: 1.0,
+            backward_p: 1.0,
+        };
+    }
+    // The backward walk from position k is S_n - S_{k-1}, so both maxima
+    // come from one pass over the prefix sums.
+    let (mut sum, mut max_prefix, mut min_prefix) = (0i64, 0i64, 0i64);
+    let mut forward = 0i64;
+    for i in 0..n {
+        sum += 2 * bits.bit(i) as i64 - 1;
+        forward = forward.max(sum.abs());
+        if i + 1 < n {
+            max_prefix = max_prefix.max(sum);
+            min_prefix = min_prefix.min(sum);
+        }
+    }
+    let backward = (sum - min_prefix).abs().max((sum - max_prefix).abs());
+    CusumResult {
+        forward_p: cusum_p(n as i64, forward),
+        backward_p: cusum_p(n as i64, backward),
+    }
+}
+
+/// P-value of a maximal partial-sum excursion z over n steps (SP 800-22 §2.13.4),
+/// with the summation bounds truncated toward zero as in the reference code.
+fn cusum_p(n: i64, z: i64) -> f64 {
+    let normal_cdf = |x: f64| 0.5 * erfc(-x / std::f64::consts::SQRT_2);
+    let sqrt_n = (n as f64).sqrt();
+    let z_f = z as f64;
+    let term = |k: i64, a: i64, b: i64| {
+        normal_cdf((4 * k + a) as f64 * z_f / sqrt_n)
+            - normal_cdf((4 * k + b) as f64 * z_f / sqrt_n)
+    };
+    let first: f64 = ((-n / z + 1) / 4..=(n / z - 1) / 4)
+        .map(|k| term(k, 1, -1))
+        .sum();
+    let second: f64 = ((-n / z - 3) / 4..=(n / z - 1) / 4)
+        .map(|k| term(k, 3, 1))
+        .sum();
+    1.0 - first + second
+}
+
+/// Outcome of the SP 800-22 random excursions and random excursions variant
+/// tests; both are only applicable when the walk has enough cycles.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RandomExcursionsResult {
+    /// Number of cycles J of the zero-padded random walk.
+    pub cycles: usize,
+    /// One p-value per state of `EXCURSION_STATES`.
+    pub excursion_p: Option<[f64; 8]>,
+    /// One p-value per state of `EXCURSION_VARIANT_STATES`.
+    pub variant_p: Option<[f64; 18]>,
+}
+
+/// NIST SP 800-22 §2.14 random excursions and §2.15 random excursions variant
+/// tests. The walk is split into cycles between returns to zero; the tests are
+/// skipped (`None`) when J < max(0.005·√n, 500).
+pub fn random_excursions(bits: &BitString) -> RandomExcursionsResult {
+    let walk = ExcursionCounts::from_walk(bits);
+    let constraint = (0.005 * (bits.bits as f64).sqrt()).max(EXCURSION_MIN_CYCLES as f64);
+    if (walk.cycles as f64) < constraint {
+        return RandomExcursionsResult {
+            cycles: walk.cycles,
+            excursion_p: None,
+            variant_p: None,
+        };
+    }
+    RandomExcursionsResult {
+        cycles: walk.cycles,
+        excursion_p: Some(walk.excursion_p()),
+        variant_p: Some(walk.variant_p()),
+    }
+}
+
+/// Cycle decomposition of the ±1 random walk S_1..S_n padded with zeros.
+struct ExcursionCounts {
+    cycles: usize,
+    /// ν_k(x): cycles visiting `EXCURSION_STATES[x]` exactly k times (k = 5 is "≥ 5").
+    per_cycle: [[u64; 6]; 8],
+    /// ξ(x): total visits to `EXCURSION_VARIANT_STATES[x]`.
+    total_visits: [u64; 18],
+}
+
+impl ExcursionCounts {
+    fn from_walk(bits: &BitString) -> Self {
+        let mut counts = Self {
+            cycles: 0,
+            per_cycle: [[0; 6]; 8],
+            total_visits: [0; 18],
+        };
+        let mut visits = [0usize; 8];
+        let close_cycle = |counts: &mut Self, visits: &mut [usize; 8]| {
+            counts.cycles += 1;
+            for (nu, v) in counts.per_cycle.iter_mut().zip(visits.iter_mut()) {
+                nu[(*v).min(5)] += 1;
+                *v = 0;
+            }
+        };
+        let mut sum = 0i64;
+        for i in 0..bits.bits {
+            sum += 2 * bits.bit(i) as i64 - 1;
+            match sum {
+                0 => close_cycle(&mut counts, &mut visits),
+                s if s.abs() <= 9 => {
+                    let index = if s < 0 { s + 9 } else { s + 8 } as usize;
+                    counts.total_visits[index] += 1;
+                    if s.abs() <= 4 {
+                        visits[if s < 0 { s + 4 } else { s + 3 } as usize] += 1;
+                    }
+                }
+                _ => {}
+            }
+        }
+        if sum != 0 {
+            close_cycle(&mut counts, &mut visits);
+        }
+        counts
+    }
+
+    fn excursion_p(&self) -> [f64; 8] {
+        let j = self.cycles as f64;
+        let mut p_values = [0.0; 8];
+        for ((p, nu), &x) in p_values
+            .iter_mut()
+            .zip(&self.per_cycle)
+            .zip(&EXCURSION_STATES)
+        {
+            let chi_squared: f64 = excursion_probabilities(x)
+                .iter()
+                .zip(nu)
+                .map(|(&pi, &v)| (v as f64 - j * pi).powi(2) / (j * pi))
+                .sum();
+            *p = igamc(2.5, chi_squared / 2.0);
+        }
+        p_values
+    }
+
+    fn variant_p(&self) -> [f64; 18] {
+        let j = self.cycles as f64;
+        let mut p_values = [0.0; 18];
+        for ((p, &xi), &x) in p_values
+            .iter_mut()
+            .zip(&self.total_visits)
+            .zip(&EXCURSION_VARIANT_STATES)
+        {
+            let denominator = (2.0 * j * (4.0 * x.abs() as f64 - 2.0)).sqrt();
+            *p = erfc((xi as f64 - j).abs() / denominator);
+        }
+        p_values
+    }
+}
+
+/// π_k(x), k = 0..5: the probability that a cycle visits state x exactly k
+/// times (at least 5 for k = 5), SP 800-22 §3.14.
+fn excursion_probabilities(x: i64) -> [f64; 6] {
+    let q = 1.0 / (2.0 * x.abs() as f64);
+    let mut pi = [0.0; 6];
+    pi[0] = 1.0 - q;
+    for (k, p) in pi.iter_mut().enumerate().take(5).skip(1) {
+        *p = q * q * (1.0 - q).powi(k as i32 - 1);
+    }
+    pi[5] = q * (1.0 - q).powi(4);
+    pi
+}
+
+/// A chi-square statistic and its p-value.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ChiSquareResult {
+    pub statistic: f64,
+    pub p_value: f64,
+}
+
+/// Chi-square goodness of fit of the byte histogram against the uniform
+/// distribution over 256 values (255 degrees of freedom). Trailing bits that
+/// do not fill a byte are ignored.
+pub fn byte_chi_square(bits: &BitString) -> ChiSquareResult {
+    let full_bytes = bits.bits / 8;
+    if full_bytes == 0 {
+        return ChiSquareResult {
+            statistic: 0.0,
+            p_value: 1.0,
+        };
+    }
+    let mut histogram = [0u64; 256];
+    for &byte in &bits.bytes[..full_bytes] {
+        histogram[byte as usize] += 1;
+    }
+    let expected = full_bytes as f64 / 256.0;
+    let statistic: f64 = histogram
+        .iter()
+        .map(|&f| (f as f64 - expected).powi(2) / expected)
+        .sum();
+    ChiSquareResult {
+        statistic,
+        p_value: igamc(255.0 / 2.0, statistic / 2.0),
+    }
+}
+
+/// Shannon entropy of the byte histogram, in bits per byte (8 at most).
+pub fn shannon_entropy(bits: &BitString) -> f64 {
+    let bytes = &bits.bytes[..bits.bits / 8];
+    if bytes.is_empty() {
+        return 0.0;
+    }
+    let mut histogram = [0u64; 256];
+    for &byte in bytes {
+        histogram[byte as usize] += 1;
+    }
+    let n = bytes.len() as f64;
+    -histogram
+        .iter()
+        .filter(|&&f| f > 0)
+        .map(|&f| {
+            let p = f as f64 / n;
+            p * p.log2()
+        })
+        .sum::<f64>()
+}
+
+/// The metrics of John Walker's ENT tool, computed as ENT does so results can
+/// be compared with published numbers.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct EntResults {
+    /// Shannon entropy, bits per byte.
+    pub entropy: f64,
+    pub chi_square: ChiSquareResult,
+    /// Arithmetic mean of the bytes (127.5 for random data).
+    pub mean: f64,
+    pub monte_carlo_pi: f64,
+    pub serial_correlation: f64,
+}
+
+/// ENT-equivalent suite over the whole bytes of `bits`.
+pub fn ent(bits: &BitString) -> EntResults {
+    let bytes = &bits.bytes[..bits.bits / 8];
+    let mean = if bytes.is_empty() {
+        0.0
+    } else {
+        bytes.iter().map(|&b| b as f64).sum::<f64>() / bytes.len() as f64
+    };
+
+    // A point (x, y) hits the quarter circle when x² + y² <= (2^24 - 1)².
+    let radius = (1u64 << (4 * MONTE_CARLO_BYTES)) - 1;
+    let coordinate = |c: &[u8]| c.iter().fold(0u64, |acc, &b| (acc << 8) | b as u64);
+    let mut points = 0u64;
+    let mut inside = 0u64;
+    for group in bytes.chunks_exact(MONTE_CARLO_BYTES) {
+        let (x, y) = group.split_at(MONTE_CARLO_BYTES / 2);
+        let (x, y) = (coordinate(x), coordinate(y));
+        points += 1;
+        if x * x + y * y <= radius * radius {
+            inside += 1;
+        }
+    }
+    let monte_carlo_pi = if points == 0 {
+        0.0
+    } else {
+        4.0 * inside as f64 / points as f64
+    };
+
+    EntResults {
+        entropy: shannon_entropy(bits),
+        chi_square: byte_chi_square(bits),
+        mean,
+        monte_carlo_pi,
+        serial_correlation: serial_correlation(bits),
+    }
+}
+
+/// Input size over zstd-compressed size of the whole bytes. Incompressible
+/// output sits just below 1 (framing overhead); anything above 1 means the
+/// codec found structure.
+pub fn compression_ratio(bits: &BitString) -> f64 {
+    let bytes = &bits.bytes[..bits.bits / 8];
+    if bytes.is_empty() {
+        return 1.0;
+    }
+    let compressed = zstd::bulk::compress(bytes, ZSTD_LEVEL)
+        .expect("in-memory zstd compression should not fail");
+    bytes.len() as f64 / compressed.len() as f64
+}
+
+/// Lag-1 serial correlation coefficient of the bytes, computed as ENT does
+/// (the last byte is paired with the first). Near 0 for random data; 0 when
+/// there are fewer than two bytes or all bytes are equal.
+pub fn serial_correlation(bits: &BitString) -> f64 {
+    let bytes = &bits.bytes[..bits.bits / 8];
+    let n = bytes.len();
+    if n < 2 {
+        return 0.0;
+    }
+    let (mut sum, mut sum_squares, mut sum_products) = (0.0, 0.0, 0.0);
+    for (i, &byte) in bytes.iter().enumerate() {
+        let u = byte as f64;
+        let next = bytes[(i + 1) % n] as f64;
+        sum += u;
+        sum_squares += u * u;
+        sum_products += u * next;
+    }
+    let n = n as f64;
+    let denominator = n * sum_squares - sum * sum;
+    if denominator == 0.0 {
+        0.0
+    } else {
+        (n * sum_products - sum * sum) / denominator
+    }
+}
+
+/// FIPS 140-1 poker test over non-overlapping 4-bit blocks:
+/// X = 16/k·Σf_i² - k, compared against chi-square with 15 degrees of freedom
+/// instead of the fixed 20 000-bit bounds so any length can be tested.
+pub fn poker(bits: &BitString) -> ChiSquareResult {
+    let k = bits.bits / 4;
+    if k == 0 {
+        return ChiSquareResult {
+            statistic: 0.0,
+            p_value: 1.0,
+        };
+    }
+    let mut counts = [0u64; 16];
+    for i in 0..k {
+        let byte = bits.bytes[i / 2];
+        let nibble = if i.is_multiple_of(2) {
+            byte >> 4
+        } else {
+            byte & 0x0F
+        };
+        counts[nibble as usize] += 1;
+    }
+    let sum_squares: f64 = counts.iter().map(|&f| (f * f) as f64).sum();
+    let statistic = 16.0 / k as f64 * sum_squares - k as f64;
+    ChiSquareResult {
+        statistic,
+        p_value: igamc(15.0 / 2.0, statistic / 2.0),
+    }
+}
+
+/// Outcome of the autocorrelation test at one shift.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct AutocorrelationResult {
+    pub shift: usize,
+    /// Standard normal statistic 2(A(d) - (n - d)/2)/√(n - d).
+    pub z: f64,
+    pub p_value: f64,
+}
+
+/// Autocorrelation test (HAC §5.4.4): A(d) counts the positions where the
+/// sequence differs from itself shifted by `shift` bits; two-sided p-value.
+pub fn autocorrelation(bits: &BitString, shift: usize) -> AutocorrelationResult {
+    let n = bits.bits;
+    if shift == 0 || shift >= n {
+        return AutocorrelationResult {
+            shift,
+            z: 0.0,
+            p_value: 1.0,
+        };
+    }
+
+    let m = (n - shift) as f64;
+    let z = 2.0 * (shifted_differences(bits, shift) as f64 - m / 2.0) / m.sqrt();
+    AutocorrelationResult {
+        shift,
+        z,
+        p_value: erfc(z.abs() / std::f64::consts::SQRT_2),
+    }
+}
+
+/// Autocorrelation coefficients R(d) = 1 - 2·A(d)/(n - d) of the ±1 sequence
+/// for d = 1..=max_lag; lags beyond the sequence give 0.
+pub fn lag_spectrum(bits: &BitString, max_lag: usize) -> Vec<f64> {
+    (1..=max_lag)
+        .map(|shift| {
+            if shift >= bits.bits {
+                return 0.0;
+            }
+            let comparisons = (bits.bits - shift) as f64;
+            1.0 - 2.0 * shifted_differences(bits, shift) as f64 / comparisons
+        })
+        .collect()
+}
+
+/// A(d): positions i < n - d where bit i differs from bit i + d, requiring
+/// 0 < d < n.
+fn shifted_differences(bits: &BitString, shift: usize) -> u64 {
+    // Whole bytes whose shifted counterpart is complete, then the tail bit by bit.
+    let n = bits.bits;
+    let comparisons = n - shift;
+    let (byte_shift, bit_shift) = (shift / 8, shift % 8);
+    let full_bytes = (n / 8).saturating_sub(byte_shift + 1).min(comparisons / 8);
+    let mut differences = 0u64;
+    for j in 0..full_bytes {
+        let shifted = if bit_shift == 0 {
+            bits.bytes[j + byte_shift]
+        } else {
+            (bits.bytes[j + byte_shift] << bit_shift)
+                | (bits.bytes[j + byte_shift + 1] >> (8 - bit_shift))
+        };
+        differences += (bits.bytes[j] ^ shifted).count_ones() as u64;
+    }
+    for i in full_bytes * 8..comparisons {
+        differences += (bits.bit(i) ^ bits.bit(i + shift)) as u64;
+    }
+    differences
+}
+
+/// Big-endian 32-bit words of the whole bytes of `bits`.
+fn words32(bits: &BitString) -> Vec<u32> {
+    bits.bytes[..bits.bits / 8]
+        .chunks_exact(4)
+        .map(|chunk| u32::from_be_bytes(chunk.try_into().unwrap()))
+        .collect()
+}
+
+/// Knuth's gap test (TAOCP 3.3.2 D) on U = word / 2^32: the lengths of the
+/// runs of values outside [α, β) between two values inside it, in classes
+/// 0..t-1 and >= t, against p(1 - p)^r. `None` when the rarest class would
+/// expect fewer than 5 gaps.
+pub fn gap(bits: &BitString) -> Option<ChiSquareResult> {
+    let t = GAP_CLASSES;
+    let mut counts = vec![0u64; t + 1];
+    let mut gaps = 0u64;
+    let mut length = 0usize;
+    for word in words32(bits) {
+        let u = word as f64 / 4_294_967_296.0;
+        if (GAP_ALPHA..GAP_BETA).contains(&u) {
+            counts[length.min(t)] += 1;
+            gaps += 1;
+            length = 0;
+        } else {
+            length += 1;
+        }
+    }
+
+    let p = GAP_BETA - GAP_ALPHA;
+    let probabilities: Vec<f64> = (0..=t)
+        .map(|r| {
+            if r < t {
+                p * (1.0 - p).powi(r as i32)
+            } else {
+                (1.0 - p).powi(t as i32)
+            }
+        })
+        .collect();
+    let rarest = probabilities.iter().copied().fold(1.0, f64::min);
+    if (gaps as f64) * rarest < MIN_EXPECTED_COUNT {
+        return None;
+    }
+    let statistic: f64 = counts
+        .iter()
+        .zip(&probabilities)
+        .map(|(&c, &pr)| {
+            let expected = gaps as f64 * pr;
+            (c as f64 - expected).powi(2) / expected
+        })
+        .sum();
+    Some(ChiSquareResult {
+        statistic,
+        p_value: igamc(t as f64 / 2.0, statistic / 2.0),
+    })
+}
+
+/// Overlapping 5-permutations test over 32-bit words, in the ∇ψ² form of the
+/// serial test: ψ²₅ - ψ²₄ over the cyclic windows' orderings is chi-square with
+/// 5! - 4! = 96 degrees of freedom, in place of Diehard's covariance-matrix
+/// form. `None` below 5 expected windows per ordering.
+pub fn overlapping_permutations(bits: &BitString) -> Option<ChiSquareResult> {
+    let words = words32(bits);
+    let orderings: usize = (1..=PERMUTATION_LEN).product();
+    if (words.len() as f64) < MIN_EXPECTED_COUNT * orderings as f64 {
+        return None;
+    }
+    let psi = |m: usize| {
+        let mut counts = vec![0u64; (1..=m).product()];
+        let mut window = [0u32; PERMUTATION_LEN];
+        for start in 0..words.len() {
+            for (k, slot) in window[..m].iter_mut().enumerate() {
+                *slot = words[(start + k) % words.len()];
+            }
+            counts[permutation_index(&window[..m])] += 1;
+        }
+        let n = words.len() as f64;
+        counts.len() as f64 / n * counts.iter().map(|&c| (c * c) as f64).sum::<f64>() - n
+    };
+    let statistic = psi(PERMUTATION_LEN) - psi(PERMUTATION_LEN - 1);
+    let degrees = orderings - orderings / PERMUTATION_LEN;
+    Some(ChiSquareResult {
+        statistic,
+        p_value: igamc(degrees as f64 / 2.0, statistic / 2.0),
+    })
+}
+
+/// Lehmer code of the ordering of `values`, in 0..len!; ties count as ordered.
+fn permutation_index(values: &[u32]) -> usize {
+    let m = values.len();
+    let mut index = 0;
+    for i in 0..m {
+        let smaller_after = values[i + 1..].iter().filter(|&&v| v < values[i]).count();
+        index = index * (m - i) + smaller_after;
+    }
+    index
+}
+
+/// Outcome of the birthday spacings test.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct BirthdaySpacingsResult {
+    /// Samples of 512 birthdays, counted over all bit offsets.
+    pub samples: usize,
+    /// Total number of repeated spacings J over all samples.
+    pub repeats: u64,
+    pub p_value: f64,
+}
+
+/// Diehard birthday spacings test over big-endian 64-bit words. The repeat
+/// counts of all samples add up to a Poisson variable with mean 2 per sample,
+/// which gives a two-sided p-value. Sequences shorter than 512 words get 1.0.
+pub fn birthday_spacings(bits: &BitString) -> BirthdaySpacingsResult {
+    let words: Vec<u64> = bits.bytes[..bits.bits / 8]
+        .chunks_exact(8)
+        .map(|chunk| u64::from_be_bytes(chunk.try_into().unwrap()))
+        .collect();
+
+    let mask = (1u64 << BIRTHDAY_DAY_BITS) - 1;
+    let mut samples = 0;
+    let mut repeats = 0u64;
+    let mut birthdays = vec![0u64; BIRTHDAY_COUNT];
+    let mut spacings = vec![0u64; BIRTHDAY_COUNT - 1];
+    for chunk in words.chunks_exact(BIRTHDAY_COUNT) {
+        for offset in BIRTHDAY_OFFSETS {
+            for (day, &word) in birthdays.iter_mut().zip(chunk) {
+                *day = (word >> offset) & mask;
+            }
+            birthdays.sort_unstable();
+            for (spacing, pair) in spacings.iter_mut().zip(birthdays.windows(2)) {
+                *spacing = pair[1] - pair[0];
+            }
+            spacings.sort_unstable();
+            repeats += spacings
+                .windows(2)
+                .filter(|pair| pair[0] == pair[1])
+                .count() as u64;
+            samples += 1;
+        }
+    }
+    if samples == 0 {
+        return BirthdaySpacingsResult {
+            samples,
+            repeats,
+            p_value: 1.0,
+        };
+    }
+
+    // P(X <= k) = Q(k + 1, λ) and P(X >= k) = P(k, λ) for X ~ Poisson(λ).
+    let lambda = BIRTHDAY_LAMBDA * samples as f64;
+    let lower = igamc(repeats as f64 + 1.0, lambda);
+    let upper = if repeats == 0 {
+        1.0
+    } else {
+        igam(repeats as f64, lambda)
+    };
+    BirthdaySpacingsResult {
+        samples,
+        repeats,
+        p_value: (2.0 * lower.min(upper)).min(1.0),
+    }
+}
+
+/// SP 800-22 §4.2.2 uniformity of a set of p-values: a 10-bin chi-square
+/// against the uniform distribution on [0, 1].
+pub fn uniformity(p_values: &[f64]) -> f64 {
+    if p_values.is_empty() {
+        return 1.0;
+    }
+    let mut bins = [0u64; 10];
+    for &p in p_values {
+        bins[((p * 10.0) as usize).min(9)] += 1;
+    }
+    let expected = p_values.len() as f64 / 10.0;
+    let chi_squared: f64 = bins
+        .iter()
+        .map(|&f| (f as f64 - expected).powi(2) / expected)
+        .sum();
+    igamc(9.0 / 2.0, chi_squared / 2.0)
+}
+
+/// Kolmogorov–Smirnov test of a set of p-values against the uniform
+/// distribution on [0, 1], using the asymptotic Kolmogorov distribution with
+/// Stephens' small-sample correction.
+pub fn ks_uniformity(p_values: &[f64]) -> f64 {
+    if p_values.is_empty() {
+        return 1.0;
+    }
+    let mut sorted = p_values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let n = sorted.len() as f64;
+    let d = sorted
+        .iter()
+        .enumerate()
+        .map(|(i, &p)| ((i as f64 + 1.0) / n - p).max(p - i as f64 / n))
+        .fold(0.0, f64::max);
+
+    // Q_KS(λ) = 2 Σ_{j>=1} (-1)^(j-1) exp(-2 j² λ²)
+    let lambda = (n.sqrt() + 0.12 + 0.11 / n.sqrt()) * d;
+    let mut q = 0.0;
+    let mut sign = 1.0;
+    for j in 1..=100 {
+        let term = sign * (-2.0 * (j * j) as f64 * lambda * lambda).exp();
+        q += term;
+        if term.abs() <= 1e-12 * q.abs() {
+            break;
+        }
+        sign = -sign;
+    }
+    (2.0 * q).clamp(0.0, 1.0)
+}
+
+/// P(X <= k) for X ~ Binomial(n, p), summed term by term in log space.
+pub fn binomial_cdf(k: usize, n: usize, p: f64) -> f64 {
+    if k >= n {
+        return 1.0;
+    }
+    let ln_n_factorial = ln_gamma(n as f64 + 1.0);
+    let (ln_p, ln_q) = (p.ln(), (1.0 - p).ln());
+    let cdf: f64 = (0..=k)
+        .map(|i| {
+            let ln_choose =
+                ln_n_factorial - ln_gamma(i as f64 + 1.0) - ln_gamma((n - i) as f64 + 1.0);
+            (ln_choose + i as f64 * ln_p + (n - i) as f64 * ln_q).exp()
+        })
+        .sum();
+    cdf.min(1.0)
+}
+
+/// Welch's unequal-variances t-test of two samples.
+#[derive(Debug, Clone, Copy)]
+pub struct WelchResult {
+    pub t: f64,
+    /// Welch–Satterthwaite degrees of freedom.
+    pub df: f64,
+    /// Two-sided p-value.
+    pub p_value: f64,
+    /// Cohen's d with the pooled standard deviation; positive when `a` has
+    /// the larger mean.
+    pub cohens_d: f64,
+}
+
+/// Mann–Whitney U test of two samples.
+#[derive(Debug, Clone, Copy)]
+pub struct MannWhitneyResult {
+    /// U of the first sample: the pairs (x in a, y in b) with x > y, ties
+    /// counted as half.
+    pub u: f64,
+    /// Two-sided p-value from the tie-corrected normal approximation, with
+    /// continuity correction.
+    pub p_value: f64,
+    /// Rank-biserial correlation 2U/(n_a n_b) - 1, in [-1, 1]; positive when
+    /// `a` tends to be larger.
+    pub rank_biserial: f64,
+}
+
+/// Welch's t-test; both samples need at least two values.
+pub fn welch_t_test(a: &[f64], b: &[f64]) -> WelchResult {
+    let (na, nb) = (a.len() as f64, b.len() as f64);
+    let (mean_a, var_a) = mean_variance(a);
+    let (mean_b, var_b) = mean_variance(b);
+    let (se_a, se_b) = (var_a / na, var_b / nb);
+    let se = (se_a + se_b).sqrt();
+    let pooled = (((na - 1.0) * var_a + (nb - 1.0) * var_b) / (na + nb - 2.0)).sqrt();
+    let cohens_d = if pooled > 0.0 {
+        (mean_a - mean_b) / pooled
+    } else {
+        0.0
+    };
+    if se == 0.0 {
+        let p_value = if mean_a == mean_b { 1.0 } else { 0.0 };
+        return WelchResult {
+            t: 0.0,
+            df: na + nb - 2.0,
+            p_value,
+            cohens_d,
+        };
+    }
+
+    let t = (mean_a - mean_b) / se;
+    let df = (se_a + se_b).powi(2) / (se_a * se_a / (na - 1.0) + se_b * se_b / (nb - 1.0));
+    WelchResult {
+        t,
+        df,
+        p_value: (2.0 * student_t_cdf(-t.abs(), df)).min(1.0),
+        cohens_d,
+    }
+}
+
+fn mean_variance(values: &[f64]) -> (f64, f64) {
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0);
+    (mean, variance)
+}
+
+/// Mann–Whitney U test, ranking the pooled samples with ties at their mean rank.
+pub fn mann_whitney_u(a: &[f64], b: &[f64]) -> MannWhitneyResult {
+    let (na, nb) = (a.len() as f64, b.len() as f64);
+    let n = na + nb;
+    let mut pooled: Vec<(f64, bool)> = a
+        .iter()
+        .map(|&v| (v, true))
+        .chain(b.iter().map(|&v| (v, false)))
+        .collect();
+    pooled.sort_by(|x, y| x.0.total_cmp(&y.0));
+
+    let mut rank_sum_a = 0.0;
+    let mut tie_term = 0.0;
+    let mut start = 0;
+    for group in pooled.chunk_by(|x, y| x.0 == y.0) {
+        let size = group.len() as f64;
+        // Ranks start + 1 ..= start + size, averaged.
+        let rank = start as f64 + (size + 1.0) / 2.0;
+        rank_sum_a += rank * group.iter().filter(|(_, in_a)| *in_a).count() as f64;
+        tie_term += size * size * size - size;
+        start += group.len();
+    }
+
+    let u = rank_sum_a - na * (na + 1.0) / 2.0;
+    let mean = na * nb / 2.0;
+    let variance = na * nb / 12.0 * ((n + 1.0) - tie_term / (n * (n - 1.0)));
+    let p_value = if variance > 0.0 {
+        let z = ((u - mean).abs() - 0.5).max(0.0) / variance.sqrt();
+        erfc(z / std::f64::consts::SQRT_2).min(1.0)
+    } else {
+        1.0
+    };
+    MannWhitneyResult {
+        u,
+        p_value,
+        rank_biserial: 2.0 * u / (na * nb) - 1.0,
+    }
+}
+
+/// Student's t distribution function with `df` degrees of freedom, via
+/// P(T <= -t) = I_{df/(df + t²)}(df/2, 1/2) / 2 for t ≥ 0.
+pub fn student_t_cdf(t: f64, df: f64) -> f64 {
+    let tail = 0.5 * incbet(0.5 * df, 0.5, df / (df + t * t));
+    if t > 0.0 { 1.0 - tail } else { tail }
+}
+
+/// Inverse of [`student_t_cdf`], by bisection.
+pub fn student_t_quantile(p: f64, df: f64) -> f64 {
+    if p < 0.5 {
+        return -student_t_quantile(1.0 - p, df);
+    }
+    let mut high = 1.0;
+    while student_t_cdf(high, df) < p {
+        high *= 2.0;
+    }
+    let mut low = 0.0;
+    for _ in 0..100 {
+        let mid = 0.5 * (low + high);
+        if student_t_cdf(mid, df) < p {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    0.5 * (low + high)
+}
+
+/// Complementary error function, via erfc(x) = Q(1/2, x²) for x ≥ 0.
+pub fn erfc(x: f64) -> f64 {
+    let q = igamc(0.5, x * x);
+    if x < 0.0 { 2.0 - q } else { q }
+}
+
+/// Regularized upper incomplete gamma function Q(a, x), as in Cephes `igamc`.
+pub fn igamc(a: f64, x: f64) -> f64 {
+    if x <= 0.0 || a <= 0.0 {
+        return 1.0;
+    }
+    if x < 1.0 || x < a {
+        return 1.0 - igam(a, x);
+    }
+    let ax = a * x.ln() - x - ln_gamma(a);
+    if ax < -MAXLOG {
+        return 0.0;
+    }
+    let ax = ax.exp();
+
+    let mut y = 1.0 - a;
+    let mut z = x + y + 1.0;
+    let mut c = 0.0;
+    let (mut pkm2, mut qkm2) = (1.0, x);
+    let (mut pkm1, mut qkm1) = (x + 1.0, z * x);
+    let mut ans = pkm1 / qkm1;
+    loop {
+        c += 1.0;
+        y += 1.0;
+        z += 2.0;
+        let yc = y * c;
+        let pk = pkm1 * z - pkm2 * yc;
+        let qk = qkm1 * z - qkm2 * yc;
+        let t = if qk != 0.0 {
+            let r = pk / qk;
+            let t = ((ans - r) / r).abs();
+            ans = r;
+            t
+        } else {
+            1.0
+        };
+        (pkm2, pkm1) = (pkm1, pk);
+        (qkm2, qkm1) = (qkm1, qk);
+        if pk.abs() > BIG {
+            pkm2 *= BIG_INV;
+            pkm1 *= BIG_INV;
+            qkm2 *= BIG_INV;
+            qkm1 *= BIG_INV;
+        }
+        if t <= MACHEP {
+            break;
+        }
+    }
+    ans * ax
+}
+
+/// Regularized lower incomplete gamma function P(a, x), as in Cephes `igam`.
+pub fn igam(a: f64, x: f64) -> f64 {
+    if x <= 0.0 || a <= 0.0 {
+        return 0.0;
+    }
+    if x > 1.0 && x > a {
+        return 1.0 - igamc(a, x);
+    }
+    let ax = a * x.ln() - x - ln_gamma(a);
+    if ax < -MAXLOG {
+        return 0.0;
+    }
+    let ax = ax.exp();
+
+    let mut r = a;
+    let mut c = 1.0;
+    let mut ans = 1.0;
+    loop {
+        r += 1.0;
+        c *= x / r;
+        ans += c;
+        if c / ans <= MACHEP {
+            break;
+        }
+    }
+    ans * ax / a
+}
+
+/// Regularized incomplete beta function I_x(a, b), from the continued fraction
+/// on whichever side of the mean converges faster.
+pub fn incbet(a: f64, b: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if x >= 1.0 {
+        return 1.0;
+    }
+    let front =
+        (ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b) + a * x.ln() + b * (1.0 - x).ln()).exp();
+    if x < (a + 1.0) / (a + b + 2.0) {
+        front * beta_fraction(a, b, x) / a
+    } else {
+        1.0 - front * beta_fraction(b, a, 1.0 - x) / b
+    }
+}
+
+/// Continued fraction for I_x(a, b), evaluated by the modified Lentz method.
+fn beta_fraction(a: f64, b: f64, x: f64) -> f64 {
+    let tiny = f64::MIN_POSITIVE / MACHEP;
+    let guard = |v: f64| if v.abs() < tiny { tiny } else { v };
+    let mut c = 1.0;
+    let mut d = 1.0 / guard(1.0 - (a + b) * x / (a + 1.0));
+    let mut h = d;
+    for m in 1..=300 {
+        let m = m as f64;
+        let even = m * (b - m) * x / ((a + 2.0 * m - 1.0) * (a + 2.0 * m));
+        d = 1.0 / guard(1.0 + even * d);
+        c = guard(1.0 + even / c);
+        h *= d * c;
+        let odd = -(a + m) * (a + b + m) * x / ((a + 2.0 * m) * (a + 2.0 * m + 1.0));
+        d = 1.0 / guard(1.0 + odd * d);
+        c = guard(1.0 + odd / c);
+        h *= d * c;
+        if (d * c - 1.0).abs() <= MACHEP {
+            break;
+        }
+    }
+    h
+}
+
+/// ln Γ(x) for x > 0 by the Lanczos approximation, reflected below 1/2.
+pub fn ln_gamma(x: f64) -> f64 {
+    if x < 0.5 {
+        let pi = std::f64::consts::PI;
+        return (pi / (pi * x).sin()).ln() - ln_gamma(1.0 - x);
+    }
+    let x = x - 1.0;
+    let mut sum = LANCZOS_COEFFICIENTS[0];
+    for (i, &coefficient) in LANCZOS_COEFFICIENTS.iter().enumerate().skip(1) {
+        sum += coefficient / (x + i as f64);
+    }
+    let t = x + LANCZOS_G + 0.5;
+    0.5 * (2.0 * std::f64::consts::PI).ln() + (x + 0.5) * t.ln() - t + sum.ln()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A bit string from the '0'/'1' notation of the SP 800-22 examples.
+    fn bit_string(text: &str) -> BitString {
+        let mut bytes = vec![0u8; text.len().div_ceil(8)];
+        for (i, c) in text.chars().enumerate() {
+            if c == '1' {
+                bytes[i / 8] |= 0x80 >> (i % 8);
+            }
+        }
+        BitString {
+            bits: text.len(),
+            bytes,
+        }
+    }
+
+    fn assert_close(actual: f64, expected: f64, tolerance: f64) {
+        assert!(
+            (actual - expected).abs() <= tolerance,
+            "{actual} is not within {tolerance} of {expected}"
+        );
+    }
+
+    /// The examples give p-values to six decimals.
+    const EXAMPLE_TOLERANCE: f64 = 1e-6;
+
+    /// SP 800-22 §2.1.4.
+    #[test]
+    fn monobit_example() {
+        assert_close(
+            monobit(&bit_string("1011010101")),
+            0.527089,
+            EXAMPLE_TOLERANCE,
+        );
+    }
+
+    #[test]
+    fn erfc_known_values() {
+        assert_close(erfc(0.0), 1.0, 1e-15);
+        assert_close(erfc(1.0), 0.157_299_207_050_285_1, 1e-14);
+        assert_close(erfc(-1.0), 1.842_700_792_949_715, 1e-14);
+        assert_close(erfc(2.0), 0.004_677_734_981_047_266, 1e-15);
+    }
+
+    /// For integer a, Q(a, x) = e^-x Σ_{k<a} x^k / k!; both the series
+    /// (x < a) and continued-fraction (x ≥ a) branches.
+    #[test]
+    fn igamc_known_values() {
+        assert_close(igamc(1.0, 2.0), (-2f64).exp(), 1e-15);
+        assert_close(igamc(2.0, 3.0), 4.0 * (-3f64).exp(), 1e-15);
+        assert_close(igamc(5.0, 2.0), 7.0 * (-2f64).exp(), 1e-14);
+        assert_close(igamc(0.5, 4.0), erfc(2.0), 1e-15);
+        assert_eq!(igamc(3.0, 0.0), 1.0);
+    }
+
+    /// SP 800-22 §2.2.4.
+    #[test]
+    fn block_frequency_example() {
+        assert_close(
+            block_frequency(&bit_string("0110011010"), 3),
+            0.801252,
+            EXAMPLE_TOLERANCE,
+        );
+    }
+
+    /// SP 800-22 §2.3.4.
+    #[test]
+    fn runs_example() {
+        let result = runs(&bit_string("1001101011"));
+        assert_eq!(result.runs, 7);
+        assert_close(result.p_value, 0.147232, EXAMPLE_TOLERANCE);
+    }
+
+    /// SP 800-22 §2.4.8, with M = 8 for a 128-bit sequence.
+    #[test]
+    fn longest_run_example() {
+        let bits = bit_string(concat!(
+            "11001100000101010110110001001100111000000000001001001101010100010001001111010110",
+            "100000001101011111001100111001101101100010110010",
+        ));
+        assert_close(longest_run(&bits), 0.180609, EXAMPLE_TOLERANCE);
+    }
+
+    /// SP 800-22 §2.7.4: m = 3, N = 2, template 001 (the first aperiodic one).
+    #[test]
+    fn non_overlapping_template_example() {
+        assert_eq!(aperiodic_templates(3), [0b001, 0b011, 0b100, 0b110]);
+        let p = non_overlapping_template_p(&bit_string("10100100101110010110"), 3, 2);
+        assert_close(p[0], 0.344154, EXAMPLE_TOLERANCE);
+    }
+
+    /// `OVERLAPPING_PROBABILITIES` against the exact distribution of the number
+    /// of all-ones m-bit matches in a block of uniform bits, by dynamic
+    /// programming over (trailing ones, matches so far).
+    #[test]
+    fn overlapping_template_probabilities_are_exact() {
+        let m = OVERLAPPING_TEMPLATE_LENGTH;
+        let classes = OVERLAPPING_PROBABILITIES.len();
+        let mut state = vec![vec![0.0f64; classes]; m];
+        state[0][0] = 1.0;
+        for _ in 0..OVERLAPPING_BLOCK_LEN {
+            let mut next = vec![vec![0.0f64; classes]; m];
+            for (run, row) in state.iter().enumerate() {
+                for (matches, &p) in row.iter().enumerate() {
+                    next[0][matches] += p / 2.0;
+                    if run + 1 < m {
+                        next[run + 1][matches] += p / 2.0;
+                    } else {
+                        next[m - 1][(matches + 1).min(classes - 1)] += p / 2.0;
+                    }
+                }
+            }
+            state = next;
+        }
+        for (class, &expected) in OVERLAPPING_PROBABILITIES.iter().enumerate() {
+            let exact: f64 = state.iter().map(|row| row[class]).sum();
+            assert_close(exact, expected, 5e-7);
+        }
+    }
+
+    /// SP 800-22 §2.11.4, m = 3.
+    #[test]
+    fn serial_example() {
+        let result = serial_with(&bit_string("0011011101"), 3);
+        assert_close(result.p_value_1, 0.808792, EXAMPLE_TOLERANCE);
+        assert_close(result.p_value_2, 0.670320, EXAMPLE_TOLERANCE);
+    }
+
+    /// SP 800-22 §2.12.4, m = 3.
+    #[test]
+    fn approximate_entropy_example() {
+        assert_close(
+            approximate_entropy(&bit_string("0100110101"), 3),
+            0.261961,
+            EXAMPLE_TOLERANCE,
+        );
+    }
+
+    /// SP 800-22 §2.13.8, on the first 100 bits of the binary expansion of π.
+    #[test]
+    fn cusum_example() {
+        let result = cusum(&bit_string(concat!(
+            "11001001000011111101101010100010001000010110100011000010001101001100",
+            "01001100011001100010100010111000",
+        )));
+        assert_close(result.forward_p, 0.219194, EXAMPLE_TOLERANCE);
+        assert_close(result.backward_p, 0.114866, EXAMPLE_TOLERANCE);
+    }
+
+    #[test]
+    fn student_t_quantile_known_values() {
+        assert_close(student_t_quantile(0.975, 1.0), 12.706_204_736_174_7, 1e-9);
+        assert_close(student_t_quantile(0.975, 10.0), 2.228_138_851_964_938, 1e-9);
+        assert_close(student_t_quantile(0.995, 30.0), 2.749_995_653_567_12, 1e-9);
+        assert_close(
+            student_t_quantile(0.025, 10.0),
+            -2.228_138_851_964_938,
+            1e-9,
+        );
+        assert_close(student_t_quantile(0.5, 4.0), 0.0, 1e-12);
+    }
+}
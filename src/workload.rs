@@ -0,0 +1,116 @@
+//! Mixed-workload benchmark: every generator serves the same sequence of
+//! requests with log-normally distributed sizes, modelling the mix of nonce,
+//! key and record-sized requests issued by TLS stacks and key generation.
+
+use crate::config::Config;
+use crate::latency::{quantile, time_requests};
+use rand_core::{RngCore, SeedableRng};
+use rand_pcg::Pcg64;
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+
+/// Log-normal request sizes in bytes: ln(size) ~ N(ln(median), sigma²).
+#[derive(Debug, Clone, Copy)]
+pub struct RequestSizes {
+    pub median_bytes: f64,
+    pub sigma: f64,
+}
+
+pub const DEFAULT_DISTRIBUTION: RequestSizes = RequestSizes {
+    median_bytes: 64.0,
+    sigma: 1.0,
+};
+// Sizes are clamped to one SP 800-90A request.
+const MAX_REQUEST_BYTES: usize = 1 << 16;
+const REQUESTS: usize = 100_000;
+// Seed of the request-size sequence, shared by all generators.
+const SIZE_SEED: u64 = 64;
+
+impl fmt::Display for RequestSizes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "lognormal(median={} B; sigma={})",
+            self.median_bytes, self.sigma
+        )
+    }
+}
+
+impl RequestSizes {
+    /// `count` request sizes in bytes, reproducible for a given `seed`.
+    pub fn sample(&self, count: usize, seed: u64) -> Vec<usize> {
+        let mut rng = Pcg64::seed_from_u64(seed);
+        // Uniform on (0, 1], so the logarithm below stays finite.
+        let mut unit = || ((rng.next_u64() >> 11) + 1) as f64 / (1u64 << 53) as f64;
+        (0..count)
+            .map(|_| {
+                // Box–Muller.
+                let z = (-2.0 * unit().ln()).sqrt() * (2.0 * std::f64::consts::PI * unit()).cos();
+                let bytes = (self.median_bytes.ln() + self.sigma * z).exp().round() as usize;
+                bytes.clamp(1, MAX_REQUEST_BYTES)
+            })
+            .collect()
+    }
+}
+
+pub struct WorkloadSample {
+    pub generator: String,
+    pub distribution: RequestSizes,
+    pub cryptographic: bool,
+    pub requests: usize,
+    pub total_bytes: usize,
+    /// Per-request latencies in nanoseconds, sorted ascending.
+    pub latencies_ns: Vec<u64>,
+}
+
+/// Serves the first `REQUESTS` sizes of the shared sequence drawn from
+/// `distribution` with every generator, subject to the latency benchmark's
+/// time budget per series.
+pub fn run(config: &Config, distribution: RequestSizes) -> Vec<WorkloadSample> {
+    let sizes = distribution.sample(REQUESTS, SIZE_SEED);
+    let mut samples = Vec::new();
+    for mut drbg in config.build_generators(&config.seed_for(0, 0)) {
+        let mut latencies_ns = time_requests(drbg.as_mut(), sizes.iter().map(|&b| 8 * b));
+        let requests = latencies_ns.len();
+        latencies_ns.sort_unstable();
+        samples.push(WorkloadSample {
+            generator: drbg.name().to_string(),
+            distribution,
+            cryptographic: drbg.is_cryptographic(),
+            requests,
+            total_bytes: sizes[..requests].iter().sum(),
+            latencies_ns,
+        });
+    }
+    samples
+}
+
+pub fn write_csv(dir: &Path, samples: &[WorkloadSample]) -> Result<(), Box<dyn Error>> {
+    let mut file = File::create(dir.join("workload.csv"))?;
+    writeln!(
+        file,
+        "generator,cryptographic,distribution,requests,total_bytes,throughput_mb_s,mean_ns,median_ns,p99_ns,p999_ns,max_ns"
+    )?;
+    for s in samples {
+        let total_ns: u64 = s.latencies_ns.iter().sum();
+        writeln!(
+            file,
+            "{},{},{},{},{},{:.3},{:.1},{},{},{},{}",
+            s.generator,
+            s.cryptographic,
+            s.distribution,
+            s.requests,
+            s.total_bytes,
+            s.total_bytes as f64 / 1e6 / (total_ns as f64 / 1e9),
+            total_ns as f64 / s.requests as f64,
+            quantile(&s.latencies_ns, 0.5),
+            quantile(&s.latencies_ns, 0.99),
+            quantile(&s.latencies_ns, 0.999),
+            s.latencies_ns.last().copied().unwrap_or_default()
+        )?;
+    }
+    Ok(())
+}